@@ -1,16 +1,17 @@
 #![allow(dead_code)]
 
-use sdl3_sys::gpu::{
-    self, SDL_GPUBufferRegion, SDL_GPUTextureRegion, SDL_GPUTextureTransferInfo,
-    SDL_GPUTransferBufferLocation, SDL_MapGPUTransferBuffer, SDL_UnmapGPUTransferBuffer,
-};
+use sdl3_sys::gpu::{self, SDL_MapGPUTransferBuffer, SDL_UnmapGPUTransferBuffer};
 use std::{
     cmp::PartialEq,
     sync::{Arc, Mutex},
 };
 
 use crate::{
-    command_buffer::CommandBuffer, device::GPUDevice, texture::GPUTexture,
+    command_buffer::CommandBuffer,
+    device::GPUDevice,
+    error::Error,
+    readback::GPUReadback,
+    texture::{GPUTexture, GPUTextureRegion},
     texture_utils::gpu_texture_format_to_byte_size,
 };
 
@@ -74,62 +75,43 @@ pub struct GPUTransferBuffer {
 }
 
 impl GPUTransferBuffer {
+    /// Download the texture into this transfer buffer and block until the data is available.
     pub fn read_texture(
-        &self,
+        self: &Arc<Self>,
+        device: &Arc<GPUDevice>,
+        texture: &Arc<Mutex<GPUTexture>>,
+    ) -> Result<Vec<u8>, Error> {
+        self.read_texture_async(device, texture)?.wait()
+    }
+
+    /// Download the texture into this transfer buffer without waiting for the GPU.
+    /// The returned handle yields the bytes once the copy has finished executing.
+    pub fn read_texture_async(
+        self: &Arc<Self>,
         device: &Arc<GPUDevice>,
         texture: &Arc<Mutex<GPUTexture>>,
-    ) -> Result<Vec<u8>, String> {
+    ) -> Result<GPUReadback, Error> {
         if self.usage != GPUTransferUsage::Download {
-            return Err("Transfer buffer is not set to download".to_string());
+            return Err(Error::InvalidUsage(
+                "Transfer buffer is not set to download".to_string(),
+            ));
         }
 
         let texture = texture.lock().unwrap();
 
         let byte_size = gpu_texture_format_to_byte_size(texture.format) as usize;
-        let mut data = vec![0; texture.size.w as usize * texture.size.h as usize * byte_size];
-
-        let mut region: SDL_GPUTextureRegion = unsafe { std::mem::zeroed() };
-        region.texture = texture.texture;
-        region.x = 0;
-        region.y = 0;
-        region.w = texture.size.w as u32;
-        region.h = texture.size.h as u32;
-        region.z = 0;
-        region.d = 1;
-
-        let mut location: SDL_GPUTextureTransferInfo = unsafe { std::mem::zeroed() };
-        location.transfer_buffer = self.buffer;
-        location.offset = 0;
-        location.pixels_per_row = texture.size.w as u32;
-        location.rows_per_layer = texture.size.h as u32;
-
-        let mut command_buffer = device.acquire_command_buffer().unwrap();
-        let copypass = device.begin_copypass(&mut command_buffer).unwrap();
+        let size = texture.size.w as usize * texture.size.h as usize * byte_size;
+
+        let mut command_buffer = device.acquire_command_buffer()?;
+        let copypass = device.begin_copypass(&mut command_buffer)?;
         let mut copypass = copypass.lock().unwrap();
 
-        unsafe { gpu::SDL_DownloadFromGPUTexture(copypass.copypass, &region, &location) };
+        copypass.download_from_texture(&texture, &GPUTextureRegion::full(&texture), self, 0)?;
 
         copypass.end();
-        let res = command_buffer.submit();
-        if let Err(e) = res {
-            return Err(e);
-        }
-
-        let mapped_data = unsafe { SDL_MapGPUTransferBuffer(self.device, self.buffer, true) };
+        let fence = command_buffer.submit_and_acquire_fence()?;
 
-        if mapped_data.is_null() {
-            return Err("Failed to map transfer buffer".to_string());
-        }
-
-        let data_ptr = mapped_data as *const u8;
-        let data_slice = unsafe { std::slice::from_raw_parts(data_ptr, data.len()) };
-        data.copy_from_slice(data_slice);
-
-        unsafe {
-            SDL_UnmapGPUTransferBuffer(self.device, self.buffer);
-        };
-
-        Ok(data)
+        Ok(GPUReadback::new(fence, Arc::clone(self), size))
     }
 
     pub fn write_texture(
@@ -137,37 +119,26 @@ impl GPUTransferBuffer {
         device: &Arc<GPUDevice>,
         texture: &Arc<Mutex<GPUTexture>>,
         data: &[u8],
-    ) -> Result<(), String> {
+    ) -> Result<(), Error> {
         if self.usage != GPUTransferUsage::Upload {
-            return Err("Transfer buffer is not set to upload".to_string());
+            return Err(Error::InvalidUsage(
+                "Transfer buffer is not set to upload".to_string(),
+            ));
         }
 
         let texture = texture.lock().unwrap();
 
         let byte_size = gpu_texture_format_to_byte_size(texture.format) as usize;
         if data.len() != texture.size.w as usize * texture.size.h as usize * byte_size {
-            return Err("Data size does not match texture size".to_string());
+            return Err(Error::SizeMismatch(
+                "Data size does not match texture size".to_string(),
+            ));
         }
 
-        let mut region: SDL_GPUTextureRegion = unsafe { std::mem::zeroed() };
-        region.texture = texture.texture;
-        region.x = 0;
-        region.y = 0;
-        region.w = texture.size.w as u32;
-        region.h = texture.size.h as u32;
-        region.z = 0;
-        region.d = 1;
-
-        let mut location: SDL_GPUTextureTransferInfo = unsafe { std::mem::zeroed() };
-        location.transfer_buffer = self.buffer;
-        location.offset = 0;
-        location.pixels_per_row = texture.size.w as u32;
-        location.rows_per_layer = texture.size.h as u32;
-
         let mapped_data = unsafe { SDL_MapGPUTransferBuffer(self.device, self.buffer, false) };
 
         if mapped_data.is_null() {
-            return Err("Failed to map transfer buffer".to_string());
+            return Err(Error::sdl("Failed to map transfer buffer"));
         }
 
         let data_ptr = mapped_data as *mut u8;
@@ -178,11 +149,11 @@ impl GPUTransferBuffer {
             SDL_UnmapGPUTransferBuffer(self.device, self.buffer);
         };
 
-        let mut command_buffer = device.acquire_command_buffer().unwrap();
-        let copypass = device.begin_copypass(&mut command_buffer).unwrap();
+        let mut command_buffer = device.acquire_command_buffer()?;
+        let copypass = device.begin_copypass(&mut command_buffer)?;
         let mut copypass = copypass.lock().unwrap();
 
-        unsafe { gpu::SDL_UploadToGPUTexture(copypass.copypass, &location, &region, true) };
+        copypass.upload_to_texture(self, 0, &texture, &GPUTextureRegion::full(&texture), true)?;
 
         copypass.end();
         let res = command_buffer.submit();
@@ -193,48 +164,75 @@ impl GPUTransferBuffer {
         Ok(())
     }
 
+    /// Download the buffer into this transfer buffer and block until the data is available.
     pub fn read_buffer(
-        &self,
+        self: &Arc<Self>,
         device: &Arc<GPUDevice>,
         buffer: &Arc<Mutex<GPUBuffer>>,
-    ) -> Result<Vec<u8>, String> {
+    ) -> Result<Vec<u8>, Error> {
+        self.read_buffer_async(device, buffer)?.wait()
+    }
+
+    /// Download the buffer into this transfer buffer without waiting for the GPU.
+    /// The returned handle yields the bytes once the copy has finished executing.
+    pub fn read_buffer_async(
+        self: &Arc<Self>,
+        device: &Arc<GPUDevice>,
+        buffer: &Arc<Mutex<GPUBuffer>>,
+    ) -> Result<GPUReadback, Error> {
         if self.usage != GPUTransferUsage::Download {
-            return Err("Transfer buffer is not set to download".to_string());
+            return Err(Error::InvalidUsage(
+                "Transfer buffer is not set to download".to_string(),
+            ));
         }
 
         let buffer = buffer.lock().unwrap();
 
         if buffer.size > self.size {
-            return Err("Buffer size is larger than transfer buffer size".to_string());
+            return Err(Error::SizeMismatch(
+                "Buffer size is larger than transfer buffer size".to_string(),
+            ));
         }
 
-        let mut data = vec![0; buffer.size as usize];
+        let mut command_buffer = device.acquire_command_buffer()?;
+        let copypass = device.begin_copypass(&mut command_buffer)?;
+        let mut copypass = copypass.lock().unwrap();
 
-        let mut location: SDL_GPUTransferBufferLocation = unsafe { std::mem::zeroed() };
-        location.transfer_buffer = self.buffer;
-        location.offset = 0;
+        copypass.download_from_buffer(&buffer, 0, self, 0, buffer.size)?;
 
-        let mut region: SDL_GPUBufferRegion = unsafe { std::mem::zeroed() };
-        region.buffer = buffer.buffer;
-        region.offset = 0;
-        region.size = buffer.size;
+        copypass.end();
+        let fence = command_buffer.submit_and_acquire_fence()?;
 
-        let mut command_buffer = device.acquire_command_buffer().unwrap();
-        let copypass = device.begin_copypass(&mut command_buffer).unwrap();
-        let mut copypass = copypass.lock().unwrap();
+        Ok(GPUReadback::new(
+            fence,
+            Arc::clone(self),
+            buffer.size as usize,
+        ))
+    }
 
-        unsafe { gpu::SDL_DownloadFromGPUBuffer(copypass.copypass, &region, &location) };
+    /// Map the transfer buffer and copy out the first `size` bytes.
+    /// The caller is responsible for making sure the GPU is done writing to it.
+    pub fn read_mapped(&self, size: usize) -> Result<Vec<u8>, Error> {
+        if size > self.size as usize {
+            return Err(Error::SizeMismatch(
+                "Read size is larger than transfer buffer size".to_string(),
+            ));
+        }
 
-        copypass.end();
-        let res = command_buffer.submit();
-        if let Err(e) = res {
-            return Err(e);
+        let mut data = Vec::new();
+        if let Err(err) = data.try_reserve_exact(size) {
+            return Err(Error::OutOfMemory(format!(
+                "Failed to allocate read buffer: {:?}",
+                err
+            )));
         }
 
-        let mapped_data = unsafe { SDL_MapGPUTransferBuffer(self.device, self.buffer, true) };
+        data.resize(size, 0);
+
+        let mapped_data = unsafe { SDL_MapGPUTransferBuffer(self.device, self.buffer, false) };
 
         if mapped_data.is_null() {
-            return Err("Failed to map transfer buffer".to_string());
+            return Err(Error::sdl("Failed to map transfer buffer"));
         }
 
         let data_ptr = mapped_data as *const u8;
@@ -248,6 +246,38 @@ impl GPUTransferBuffer {
         Ok(data)
     }
 
+    /// Map the transfer buffer and copy `data` in at `offset`.
+    /// With `cycle` set, SDL hands out a fresh backing buffer if the current one is still in use.
+    pub fn write_mapped(&self, offset: u32, data: &[u8], cycle: bool) -> Result<(), Error> {
+        if self.usage != GPUTransferUsage::Upload {
+            return Err(Error::InvalidUsage(
+                "Transfer buffer is not set to upload".to_string(),
+            ));
+        }
+
+        if offset as usize + data.len() > self.size as usize {
+            return Err(Error::SizeMismatch(
+                "Data size is larger than transfer buffer size".to_string(),
+            ));
+        }
+
+        let mapped_data = unsafe { SDL_MapGPUTransferBuffer(self.device, self.buffer, cycle) };
+
+        if mapped_data.is_null() {
+            return Err(Error::sdl("Failed to map transfer buffer"));
+        }
+
+        let data_ptr = unsafe { (mapped_data as *mut u8).add(offset as usize) };
+        let data_slice = unsafe { std::slice::from_raw_parts_mut(data_ptr, data.len()) };
+        data_slice.copy_from_slice(data);
+
+        unsafe {
+            SDL_UnmapGPUTransferBuffer(self.device, self.buffer);
+        };
+
+        Ok(())
+    }
+
     /// Write buffer data to the transfer buffer and then upload it to the GPU buffer.
     /// This function version used a command buffer to submit the upload operation.
     /// This is useful when you want to batch multiple operations together.
@@ -257,21 +287,25 @@ impl GPUTransferBuffer {
         buffer: &Arc<Mutex<GPUBuffer>>,
         command_buffer: &mut CommandBuffer,
         data: &[u8],
-    ) -> Result<(), String> {
+    ) -> Result<(), Error> {
         if self.usage != GPUTransferUsage::Upload {
-            return Err("Transfer buffer is not set to upload".to_string());
+            return Err(Error::InvalidUsage(
+                "Transfer buffer is not set to upload".to_string(),
+            ));
         }
 
         let buffer = buffer.lock().unwrap();
 
         if data.len() > buffer.size as usize {
-            return Err("Data size is larger than buffer size".to_string());
+            return Err(Error::SizeMismatch(
+                "Data size is larger than buffer size".to_string(),
+            ));
         }
 
         let mapped_data = unsafe { SDL_MapGPUTransferBuffer(self.device, self.buffer, false) };
 
         if mapped_data.is_null() {
-            return Err("Failed to map transfer buffer".to_string());
+            return Err(Error::sdl("Failed to map transfer buffer"));
         }
 
         let data_ptr = mapped_data as *mut u8;
@@ -282,19 +316,10 @@ impl GPUTransferBuffer {
             SDL_UnmapGPUTransferBuffer(self.device, self.buffer);
         };
 
-        let copypass = device.begin_copypass(command_buffer).unwrap();
+        let copypass = device.begin_copypass(command_buffer)?;
         let mut copypass = copypass.lock().unwrap();
 
-        let mut location: SDL_GPUTransferBufferLocation = unsafe { std::mem::zeroed() };
-        location.transfer_buffer = self.buffer;
-        location.offset = 0;
-
-        let mut region: SDL_GPUBufferRegion = unsafe { std::mem::zeroed() };
-        region.buffer = buffer.buffer;
-        region.offset = 0;
-        region.size = buffer.size;
-
-        unsafe { gpu::SDL_UploadToGPUBuffer(copypass.copypass, &location, &region, true) };
+        copypass.upload_to_buffer(self, 0, &buffer, 0, data.len() as u32, true)?;
 
         copypass.end();
 
@@ -313,9 +338,11 @@ impl GPUTransferBuffer {
         buffer: &Arc<Mutex<GPUBuffer>>,
         command_buffer: &mut CommandBuffer,
         data: &[T],
-    ) -> Result<(), String> {
+    ) -> Result<(), Error> {
         if self.usage != GPUTransferUsage::Upload {
-            return Err("Transfer buffer is not set to upload".to_string());
+            return Err(Error::InvalidUsage(
+                "Transfer buffer is not set to upload".to_string(),
+            ));
         }
 
         let data_size = std::mem::size_of::<T>() * data.len();
@@ -323,7 +350,9 @@ impl GPUTransferBuffer {
             let buffer = buffer.lock().unwrap();
 
             if data_size > buffer.size as usize {
-                return Err("Data size is larger than buffer size".to_string());
+                return Err(Error::SizeMismatch(
+                    "Data size is larger than buffer size".to_string(),
+                ));
             }
         }
 
@@ -348,22 +377,26 @@ impl GPUTransferBuffer {
         device: &Arc<GPUDevice>,
         buffer: &Arc<Mutex<GPUBuffer>>,
         data: &[u8],
-    ) -> Result<(), String> {
+    ) -> Result<(), Error> {
         if self.usage != GPUTransferUsage::Upload {
-            return Err("Transfer buffer is not set to upload".to_string());
+            return Err(Error::InvalidUsage(
+                "Transfer buffer is not set to upload".to_string(),
+            ));
         }
 
         {
             let buffer = buffer.lock().unwrap();
 
             if data.len() > buffer.size as usize {
-                return Err("Data size is larger than buffer size".to_string());
+                return Err(Error::SizeMismatch(
+                    "Data size is larger than buffer size".to_string(),
+                ));
             }
         }
 
         let command_buffer = device.acquire_command_buffer();
-        if let Err(_err) = command_buffer {
-            return Err("Failed to acquire command buffer".to_string());
+        if let Err(err) = command_buffer {
+            return Err(err);
         }
 
         let mut command_buffer = command_buffer.unwrap();
@@ -386,22 +419,26 @@ impl GPUTransferBuffer {
         device: &Arc<GPUDevice>,
         buffer: &Arc<Mutex<GPUBuffer>>,
         data: &[T],
-    ) -> Result<(), String> {
+    ) -> Result<(), Error> {
         if self.usage != GPUTransferUsage::Upload {
-            return Err("Transfer buffer is not set to upload".to_string());
+            return Err(Error::InvalidUsage(
+                "Transfer buffer is not set to upload".to_string(),
+            ));
         }
 
         {
             let buffer = buffer.lock().unwrap();
             let data_size = std::mem::size_of::<T>() * data.len();
             if data_size > buffer.size as usize {
-                return Err("Data size is larger than buffer size".to_string());
+                return Err(Error::SizeMismatch(
+                    "Data size is larger than buffer size".to_string(),
+                ));
             }
         }
 
         let command_buffer = device.acquire_command_buffer();
-        if let Err(_err) = command_buffer {
-            return Err("Failed to acquire command buffer".to_string());
+        if let Err(err) = command_buffer {
+            return Err(err);
         }
 
         let mut command_buffer = command_buffer.unwrap();
@@ -0,0 +1,146 @@
+use image::{DynamicImage, ImageFormat, Rgba32FImage, RgbaImage};
+
+use crate::{error::Error, readback::GPUReadback, texture::GPUTextureFormat};
+
+pub fn is_capturable_format(format: GPUTextureFormat) -> bool {
+    matches!(
+        format,
+        GPUTextureFormat::R8G8B8A8
+            | GPUTextureFormat::R8G8B8A8_UNORM_SRGB
+            | GPUTextureFormat::B8G8R8A8
+            | GPUTextureFormat::B8G8R8A8_UNORM_SRGB
+    )
+}
+
+/// Decode an sRGB encoded 8-bit channel to linear light.
+fn srgb_to_linear(value: u8) -> f32 {
+    let value = value as f32 / 255.0;
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// EXR stores linear values, so sRGB encoded frames are decoded first.
+/// Alpha is already linear and is only rescaled.
+fn to_linear_rgba32f(img: &RgbaImage) -> Rgba32FImage {
+    Rgba32FImage::from_fn(img.width(), img.height(), |x, y| {
+        let [r, g, b, a] = img.get_pixel(x, y).0;
+        image::Rgba([
+            srgb_to_linear(r),
+            srgb_to_linear(g),
+            srgb_to_linear(b),
+            a as f32 / 255.0,
+        ])
+    })
+}
+
+fn is_srgb_format(format: GPUTextureFormat) -> bool {
+    matches!(
+        format,
+        GPUTextureFormat::R8G8B8A8_UNORM_SRGB | GPUTextureFormat::B8G8R8A8_UNORM_SRGB
+    )
+}
+
+/// A frame copied off the GPU by `GPUDevice::capture_frame`, pending download.
+#[derive(Debug)]
+pub struct GPUFrameCapture {
+    readback: GPUReadback,
+    width: u32,
+    height: u32,
+    format: GPUTextureFormat,
+}
+
+#[allow(dead_code)]
+impl GPUFrameCapture {
+    pub fn new(readback: GPUReadback, width: u32, height: u32, format: GPUTextureFormat) -> Self {
+        GPUFrameCapture {
+            readback,
+            width,
+            height,
+            format,
+        }
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.readback.is_ready()
+    }
+
+    /// Block until the frame has been downloaded and return it as RGBA8.
+    pub fn wait_image(&mut self) -> Result<RgbaImage, Error> {
+        let data = self.readback.wait()?;
+        self.to_image(data)
+    }
+
+    /// Return the frame as RGBA8 if the download has finished, without blocking.
+    pub fn try_take_image(&mut self) -> Result<Option<RgbaImage>, Error> {
+        match self.readback.try_take()? {
+            Some(data) => self.to_image(data).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Block until the frame has been downloaded and write it to `path`.
+    /// The file format is picked from the extension: PNG, QOI or EXR.
+    pub fn save(&mut self, path: &str) -> Result<(), Error> {
+        let format = ImageFormat::from_path(path);
+        if let Err(err) = format {
+            return Err(Error::Image(format!(
+                "Failed to determine image format: {:?}",
+                err
+            )));
+        }
+
+        let format = format.unwrap();
+
+        // Reject the format before waiting, which consumes the capture.
+        if !matches!(
+            format,
+            ImageFormat::Png | ImageFormat::Qoi | ImageFormat::OpenExr
+        ) {
+            return Err(Error::UnsupportedFormat(format!(
+                "Unsupported capture format: {:?}",
+                format
+            )));
+        }
+
+        let img = self.wait_image()?;
+
+        let res = match format {
+            ImageFormat::OpenExr if is_srgb_format(self.format) => {
+                DynamicImage::ImageRgba32F(to_linear_rgba32f(&img)).save_with_format(path, format)
+            }
+            ImageFormat::OpenExr => {
+                DynamicImage::ImageRgba32F(DynamicImage::ImageRgba8(img).into_rgba32f())
+                    .save_with_format(path, format)
+            }
+            _ => img.save_with_format(path, format),
+        };
+
+        if let Err(err) = res {
+            return Err(Error::Image(format!("Failed to save image: {:?}", err)));
+        }
+
+        Ok(())
+    }
+
+    fn to_image(&self, mut data: Vec<u8>) -> Result<RgbaImage, Error> {
+        // Swapchains are commonly BGRA, image expects RGBA.
+        if matches!(
+            self.format,
+            GPUTextureFormat::B8G8R8A8 | GPUTextureFormat::B8G8R8A8_UNORM_SRGB
+        ) {
+            for pixel in data.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+
+        match RgbaImage::from_raw(self.width, self.height, data) {
+            Some(img) => Ok(img),
+            None => Err(Error::SizeMismatch(
+                "Captured data does not match frame size".to_string(),
+            )),
+        }
+    }
+}
@@ -1,8 +1,60 @@
 use std::sync::Arc;
 
-use sdl3_sys::gpu;
+use sdl3_sys::gpu::{self, SDL_GPUBlitInfo};
+
+use crate::{
+    device::GPUDevice,
+    error::Error,
+    fence::EstFence,
+    gpu_layout::GPULayout,
+    math::{Color, Rect},
+    texture::{GPUFilter, GPUFlipMode, GPULoadOp, GPUTexture},
+    texture_utils::{gpu_filter_to_sdl, gpu_flip_mode_to_sdl, gpu_load_op_to_sdl},
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct GPUBlitRegion {
+    pub mip_level: u32,
+    pub layer_or_depth_plane: u32,
+    pub rect: Rect,
+}
+
+#[allow(dead_code)]
+impl GPUBlitRegion {
+    pub fn new(rect: Rect) -> Self {
+        GPUBlitRegion {
+            mip_level: 0,
+            layer_or_depth_plane: 0,
+            rect,
+        }
+    }
+
+    /// Region covering the whole base level of the texture.
+    pub fn full(texture: &GPUTexture) -> Self {
+        Self::new(texture.size)
+    }
+}
 
-use crate::{device::GPUDevice, fence::EstFence};
+#[derive(Debug, Clone, Copy)]
+pub struct GPUBlitInfo {
+    pub load_op: GPULoadOp,
+    pub clear_color: Color,
+    pub flip_mode: GPUFlipMode,
+    pub filter: GPUFilter,
+    pub cycle: bool,
+}
+
+impl Default for GPUBlitInfo {
+    fn default() -> Self {
+        GPUBlitInfo {
+            load_op: GPULoadOp::DontCare,
+            clear_color: Color::new(0.0, 0.0, 0.0, 1.0),
+            flip_mode: GPUFlipMode::None,
+            filter: GPUFilter::Linear,
+            cycle: false,
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct CommandBuffer {
@@ -14,9 +66,9 @@ pub struct CommandBuffer {
 
 #[allow(dead_code)]
 impl CommandBuffer {
-    pub fn submit(&mut self) -> Result<(), String> {
+    pub fn submit(&mut self) -> Result<(), Error> {
         if !self.usable {
-            return Err("Command buffer is already used!".to_string());
+            return Err(Error::AlreadySubmitted);
         }
 
         unsafe {
@@ -28,14 +80,14 @@ impl CommandBuffer {
         Ok(())
     }
 
-    pub fn submit_and_acquire_fence(&mut self) -> Result<Arc<EstFence>, String> {
+    pub fn submit_and_acquire_fence(&mut self) -> Result<Arc<EstFence>, Error> {
         if !self.usable {
-            return Err("Command buffer is already used!".to_string());
+            return Err(Error::AlreadySubmitted);
         }
 
         let fence = unsafe { gpu::SDL_SubmitGPUCommandBufferAndAcquireFence(self.command_buffer) };
         if fence.is_null() {
-            return Err("Failed to submit GPU command buffer with fence".to_string());
+            return Err(Error::sdl("Failed to submit GPU command buffer with fence"));
         }
 
         self.usable = false;
@@ -46,9 +98,103 @@ impl CommandBuffer {
         }))
     }
 
-    pub fn cancel(&mut self) -> Result<(), String> {
+    /// Blit a region of the source texture into a region of the destination texture,
+    /// scaling with the given filter when the regions differ in size.
+    /// Must not be called while a render, compute or copy pass is active.
+    pub fn blit(
+        &mut self,
+        source: &GPUTexture,
+        source_region: &GPUBlitRegion,
+        destination: &GPUTexture,
+        destination_region: &GPUBlitRegion,
+        info: &GPUBlitInfo,
+    ) -> Result<(), Error> {
+        if !self.usable {
+            return Err(Error::AlreadySubmitted);
+        }
+
+        let mut blit_info: SDL_GPUBlitInfo = unsafe { std::mem::zeroed() };
+
+        let [x, y, w, h] = blit_rect_to_sdl(&source_region.rect)?;
+        blit_info.source.texture = source.texture;
+        blit_info.source.mip_level = source_region.mip_level;
+        blit_info.source.layer_or_depth_plane = source_region.layer_or_depth_plane;
+        blit_info.source.x = x;
+        blit_info.source.y = y;
+        blit_info.source.w = w;
+        blit_info.source.h = h;
+
+        let [x, y, w, h] = blit_rect_to_sdl(&destination_region.rect)?;
+        blit_info.destination.texture = destination.texture;
+        blit_info.destination.mip_level = destination_region.mip_level;
+        blit_info.destination.layer_or_depth_plane = destination_region.layer_or_depth_plane;
+        blit_info.destination.x = x;
+        blit_info.destination.y = y;
+        blit_info.destination.w = w;
+        blit_info.destination.h = h;
+
+        let color = info.clear_color;
+        blit_info.load_op = gpu_load_op_to_sdl(info.load_op);
+        blit_info.clear_color.r = color.r;
+        blit_info.clear_color.g = color.g;
+        blit_info.clear_color.b = color.b;
+        blit_info.clear_color.a = color.a;
+        blit_info.flip_mode = gpu_flip_mode_to_sdl(info.flip_mode);
+        blit_info.filter = gpu_filter_to_sdl(info.filter);
+        blit_info.cycle = info.cycle;
+
+        unsafe {
+            gpu::SDL_BlitGPUTexture(self.command_buffer, &blit_info);
+        }
+
+        Ok(())
+    }
+
+    /// Push uniform data for the vertex shader uniform buffer at `slot`. Use the
+    /// types in `gpu_layout` so `data` matches the shader's std140 block.
+    pub fn push_vertex_uniform<T: GPULayout>(&mut self, slot: u32, data: &T) -> Result<(), Error> {
         if !self.usable {
-            return Err("Command buffer is already used!".to_string());
+            return Err(Error::AlreadySubmitted);
+        }
+
+        let bytes = data.as_bytes();
+        unsafe {
+            gpu::SDL_PushGPUVertexUniformData(
+                self.command_buffer,
+                slot,
+                bytes.as_ptr() as *const std::ffi::c_void,
+                bytes.len() as u32,
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn push_fragment_uniform<T: GPULayout>(
+        &mut self,
+        slot: u32,
+        data: &T,
+    ) -> Result<(), Error> {
+        if !self.usable {
+            return Err(Error::AlreadySubmitted);
+        }
+
+        let bytes = data.as_bytes();
+        unsafe {
+            gpu::SDL_PushGPUFragmentUniformData(
+                self.command_buffer,
+                slot,
+                bytes.as_ptr() as *const std::ffi::c_void,
+                bytes.len() as u32,
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn cancel(&mut self) -> Result<(), Error> {
+        if !self.usable {
+            return Err(Error::AlreadySubmitted);
         }
 
         unsafe {
@@ -61,6 +207,17 @@ impl CommandBuffer {
     }
 }
 
+fn blit_rect_to_sdl(rect: &Rect) -> Result<[u32; 4], Error> {
+    let rect = *rect;
+    if rect.x < 0 || rect.y < 0 || rect.w < 0 || rect.h < 0 {
+        return Err(Error::InvalidUsage(
+            "Blit region must not have a negative position or size".to_string(),
+        ));
+    }
+
+    Ok([rect.x as u32, rect.y as u32, rect.w as u32, rect.h as u32])
+}
+
 impl Drop for CommandBuffer {
     fn drop(&mut self) {
         if self.usable {
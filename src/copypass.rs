@@ -1,6 +1,16 @@
 use std::ops::{Deref, DerefMut};
 
-use sdl3_sys::gpu;
+use sdl3_sys::gpu::{
+    self, SDL_GPUBufferLocation, SDL_GPUBufferRegion, SDL_GPUTextureLocation, SDL_GPUTextureRegion,
+    SDL_GPUTextureTransferInfo, SDL_GPUTransferBufferLocation,
+};
+
+use crate::{
+    buffer::{GPUBuffer, GPUTransferBuffer, GPUTransferUsage},
+    error::Error,
+    texture::{GPUTexture, GPUTextureRegion},
+    texture_utils::gpu_texture_format_to_byte_size,
+};
 
 #[derive(Debug)]
 pub struct GPUCopypass {
@@ -21,6 +31,335 @@ impl GPUCopypass {
 
         self.used = true;
     }
+
+    /// Upload `size` bytes from the transfer buffer into the GPU buffer.
+    pub fn upload_to_buffer(
+        &self,
+        source: &GPUTransferBuffer,
+        source_offset: u32,
+        destination: &GPUBuffer,
+        destination_offset: u32,
+        size: u32,
+        cycle: bool,
+    ) -> Result<(), Error> {
+        self.check_usable()?;
+        check_transfer_range(source, GPUTransferUsage::Upload, source_offset, size)?;
+        check_buffer_range(destination, destination_offset, size)?;
+
+        let mut location: SDL_GPUTransferBufferLocation = unsafe { std::mem::zeroed() };
+        location.transfer_buffer = source.buffer;
+        location.offset = source_offset;
+
+        let mut region: SDL_GPUBufferRegion = unsafe { std::mem::zeroed() };
+        region.buffer = destination.buffer;
+        region.offset = destination_offset;
+        region.size = size;
+
+        unsafe { gpu::SDL_UploadToGPUBuffer(self.copypass, &location, &region, cycle) };
+
+        Ok(())
+    }
+
+    /// Upload tightly packed pixel data from the transfer buffer into a region of the texture.
+    pub fn upload_to_texture(
+        &self,
+        source: &GPUTransferBuffer,
+        source_offset: u32,
+        destination: &GPUTexture,
+        region: &GPUTextureRegion,
+        cycle: bool,
+    ) -> Result<(), Error> {
+        self.check_usable()?;
+        check_texture_region(destination, region)?;
+        check_transfer_range(
+            source,
+            GPUTransferUsage::Upload,
+            source_offset,
+            region_byte_size(destination, region)?,
+        )?;
+
+        let location = texture_transfer_info(source, source_offset, region);
+        let region = texture_region_to_sdl(destination, region);
+
+        unsafe { gpu::SDL_UploadToGPUTexture(self.copypass, &location, &region, cycle) };
+
+        Ok(())
+    }
+
+    /// Download `size` bytes from the GPU buffer into the transfer buffer.
+    /// The data is only available once the command buffer has finished executing.
+    pub fn download_from_buffer(
+        &self,
+        source: &GPUBuffer,
+        source_offset: u32,
+        destination: &GPUTransferBuffer,
+        destination_offset: u32,
+        size: u32,
+    ) -> Result<(), Error> {
+        self.check_usable()?;
+        check_buffer_range(source, source_offset, size)?;
+        check_transfer_range(
+            destination,
+            GPUTransferUsage::Download,
+            destination_offset,
+            size,
+        )?;
+
+        let mut region: SDL_GPUBufferRegion = unsafe { std::mem::zeroed() };
+        region.buffer = source.buffer;
+        region.offset = source_offset;
+        region.size = size;
+
+        let mut location: SDL_GPUTransferBufferLocation = unsafe { std::mem::zeroed() };
+        location.transfer_buffer = destination.buffer;
+        location.offset = destination_offset;
+
+        unsafe { gpu::SDL_DownloadFromGPUBuffer(self.copypass, &region, &location) };
+
+        Ok(())
+    }
+
+    /// Download a region of the texture into the transfer buffer as tightly packed pixels.
+    /// The data is only available once the command buffer has finished executing.
+    pub fn download_from_texture(
+        &self,
+        source: &GPUTexture,
+        region: &GPUTextureRegion,
+        destination: &GPUTransferBuffer,
+        destination_offset: u32,
+    ) -> Result<(), Error> {
+        self.check_usable()?;
+        check_texture_region(source, region)?;
+        check_transfer_range(
+            destination,
+            GPUTransferUsage::Download,
+            destination_offset,
+            region_byte_size(source, region)?,
+        )?;
+
+        let location = texture_transfer_info(destination, destination_offset, region);
+        let region = texture_region_to_sdl(source, region);
+
+        unsafe { gpu::SDL_DownloadFromGPUTexture(self.copypass, &region, &location) };
+
+        Ok(())
+    }
+
+    /// Copy a region of one texture into another without going through the CPU.
+    /// Both regions must have the same extent.
+    pub fn copy_texture_to_texture(
+        &self,
+        source: &GPUTexture,
+        source_region: &GPUTextureRegion,
+        destination: &GPUTexture,
+        destination_region: &GPUTextureRegion,
+        cycle: bool,
+    ) -> Result<(), Error> {
+        self.check_usable()?;
+
+        if source_region.w != destination_region.w
+            || source_region.h != destination_region.h
+            || source_region.d != destination_region.d
+        {
+            return Err(Error::SizeMismatch(
+                "Source and destination regions must be the same size".to_string(),
+            ));
+        }
+
+        check_texture_region(source, source_region)?;
+        check_texture_region(destination, destination_region)?;
+
+        let src = texture_location_to_sdl(source, source_region);
+        let dst = texture_location_to_sdl(destination, destination_region);
+
+        unsafe {
+            gpu::SDL_CopyGPUTextureToTexture(
+                self.copypass,
+                &src,
+                &dst,
+                source_region.w,
+                source_region.h,
+                source_region.d,
+                cycle,
+            )
+        };
+
+        Ok(())
+    }
+
+    /// Copy `size` bytes from one GPU buffer into another without going through the CPU.
+    pub fn copy_buffer_to_buffer(
+        &self,
+        source: &GPUBuffer,
+        source_offset: u32,
+        destination: &GPUBuffer,
+        destination_offset: u32,
+        size: u32,
+        cycle: bool,
+    ) -> Result<(), Error> {
+        self.check_usable()?;
+        check_buffer_range(source, source_offset, size)?;
+        check_buffer_range(destination, destination_offset, size)?;
+
+        let src = SDL_GPUBufferLocation {
+            buffer: source.buffer,
+            offset: source_offset,
+        };
+
+        let dst = SDL_GPUBufferLocation {
+            buffer: destination.buffer,
+            offset: destination_offset,
+        };
+
+        unsafe { gpu::SDL_CopyGPUBufferToBuffer(self.copypass, &src, &dst, size, cycle) };
+
+        Ok(())
+    }
+
+    fn check_usable(&self) -> Result<(), Error> {
+        if self.used {
+            return Err(Error::AlreadyEnded);
+        }
+
+        Ok(())
+    }
+}
+
+fn check_transfer_range(
+    buffer: &GPUTransferBuffer,
+    usage: GPUTransferUsage,
+    offset: u32,
+    size: u32,
+) -> Result<(), Error> {
+    if buffer.usage != usage {
+        return match usage {
+            GPUTransferUsage::Upload => Err(Error::InvalidUsage(
+                "Transfer buffer is not set to upload".to_string(),
+            )),
+            GPUTransferUsage::Download => Err(Error::InvalidUsage(
+                "Transfer buffer is not set to download".to_string(),
+            )),
+        };
+    }
+
+    if offset as u64 + size as u64 > buffer.size as u64 {
+        return Err(Error::SizeMismatch(
+            "Range is out of bounds of the transfer buffer".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn check_buffer_range(buffer: &GPUBuffer, offset: u32, size: u32) -> Result<(), Error> {
+    if offset as u64 + size as u64 > buffer.size as u64 {
+        return Err(Error::SizeMismatch(
+            "Range is out of bounds of the buffer".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn check_texture_region(texture: &GPUTexture, region: &GPUTextureRegion) -> Result<(), Error> {
+    if region.mip_level >= texture.num_levels {
+        return Err(Error::SizeMismatch(
+            "Mip level is out of range of the texture".to_string(),
+        ));
+    }
+
+    if region.layer >= texture.layer_count {
+        return Err(Error::SizeMismatch(
+            "Layer is out of range of the texture".to_string(),
+        ));
+    }
+
+    let width = (texture.size.w as u32)
+        .checked_shr(region.mip_level)
+        .unwrap_or(0)
+        .max(1);
+    let height = (texture.size.h as u32)
+        .checked_shr(region.mip_level)
+        .unwrap_or(0)
+        .max(1);
+
+    // Textures are 2D (optionally layered), so the depth of every level is 1.
+    let in_bounds = |start: u32, len: u32, size: u32| match start.checked_add(len) {
+        Some(end) => end <= size,
+        None => false,
+    };
+
+    if !in_bounds(region.x, region.w, width)
+        || !in_bounds(region.y, region.h, height)
+        || !in_bounds(region.z, region.d, 1)
+    {
+        return Err(Error::SizeMismatch(
+            "Region is out of bounds of the texture".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn region_byte_size(texture: &GPUTexture, region: &GPUTextureRegion) -> Result<u32, Error> {
+    let byte_size = gpu_texture_format_to_byte_size(texture.format);
+
+    let size = region
+        .w
+        .checked_mul(region.h)
+        .and_then(|size| size.checked_mul(region.d))
+        .and_then(|size| size.checked_mul(byte_size));
+
+    match size {
+        Some(size) => Ok(size),
+        None => Err(Error::SizeMismatch(
+            "Region is too large to transfer".to_string(),
+        )),
+    }
+}
+
+fn texture_transfer_info(
+    buffer: &GPUTransferBuffer,
+    offset: u32,
+    region: &GPUTextureRegion,
+) -> SDL_GPUTextureTransferInfo {
+    let mut info: SDL_GPUTextureTransferInfo = unsafe { std::mem::zeroed() };
+    info.transfer_buffer = buffer.buffer;
+    info.offset = offset;
+    info.pixels_per_row = region.w;
+    info.rows_per_layer = region.h;
+
+    info
+}
+
+fn texture_region_to_sdl(texture: &GPUTexture, region: &GPUTextureRegion) -> SDL_GPUTextureRegion {
+    let mut res: SDL_GPUTextureRegion = unsafe { std::mem::zeroed() };
+    res.texture = texture.texture;
+    res.mip_level = region.mip_level;
+    res.layer = region.layer;
+    res.x = region.x;
+    res.y = region.y;
+    res.z = region.z;
+    res.w = region.w;
+    res.h = region.h;
+    res.d = region.d;
+
+    res
+}
+
+fn texture_location_to_sdl(
+    texture: &GPUTexture,
+    region: &GPUTextureRegion,
+) -> SDL_GPUTextureLocation {
+    let mut res: SDL_GPUTextureLocation = unsafe { std::mem::zeroed() };
+    res.texture = texture.texture;
+    res.mip_level = region.mip_level;
+    res.layer = region.layer;
+    res.x = region.x;
+    res.y = region.y;
+    res.z = region.z;
+
+    res
 }
 
 impl Deref for GPUCopypass {
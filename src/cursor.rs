@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use sdl3_sys::mouse;
+
+use crate::{error::Error, sdl::Sdl, window::with_image_surface};
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemCursor {
+    Arrow,
+    IBeam,
+    Wait,
+    Crosshair,
+    Progress,
+    ResizeNWSE,
+    ResizeNESW,
+    ResizeEW,
+    ResizeNS,
+    Move,
+    NotAllowed,
+    Hand,
+}
+
+pub fn system_cursor_to_sdl(cursor: SystemCursor) -> mouse::SDL_SystemCursor {
+    match cursor {
+        SystemCursor::Arrow => mouse::SDL_SYSTEM_CURSOR_DEFAULT,
+        SystemCursor::IBeam => mouse::SDL_SYSTEM_CURSOR_TEXT,
+        SystemCursor::Wait => mouse::SDL_SYSTEM_CURSOR_WAIT,
+        SystemCursor::Crosshair => mouse::SDL_SYSTEM_CURSOR_CROSSHAIR,
+        SystemCursor::Progress => mouse::SDL_SYSTEM_CURSOR_PROGRESS,
+        SystemCursor::ResizeNWSE => mouse::SDL_SYSTEM_CURSOR_NWSE_RESIZE,
+        SystemCursor::ResizeNESW => mouse::SDL_SYSTEM_CURSOR_NESW_RESIZE,
+        SystemCursor::ResizeEW => mouse::SDL_SYSTEM_CURSOR_EW_RESIZE,
+        SystemCursor::ResizeNS => mouse::SDL_SYSTEM_CURSOR_NS_RESIZE,
+        SystemCursor::Move => mouse::SDL_SYSTEM_CURSOR_MOVE,
+        SystemCursor::NotAllowed => mouse::SDL_SYSTEM_CURSOR_NOT_ALLOWED,
+        SystemCursor::Hand => mouse::SDL_SYSTEM_CURSOR_POINTER,
+    }
+}
+
+/// A mouse cursor shape, set with `Window::set_cursor`. Keeps SDL initialized
+/// for as long as it lives.
+#[derive(Debug)]
+pub struct Cursor {
+    cursor: *mut mouse::SDL_Cursor,
+    _sdl: Arc<Sdl>,
+}
+
+#[allow(dead_code)]
+impl Cursor {
+    pub fn new_system(sdl: &Arc<Sdl>, cursor: SystemCursor) -> Result<Self, Error> {
+        let ptr = unsafe { mouse::SDL_CreateSystemCursor(system_cursor_to_sdl(cursor)) };
+        if ptr.is_null() {
+            return Err(Error::sdl("Failed to create system cursor"));
+        }
+
+        Ok(Cursor {
+            cursor: ptr,
+            _sdl: Arc::clone(sdl),
+        })
+    }
+
+    /// Create a cursor from an image file. `hot_x` and `hot_y` are the pixel
+    /// within the image that points at the mouse position.
+    pub fn from_file(sdl: &Arc<Sdl>, path: &str, hot_x: i32, hot_y: i32) -> Result<Self, Error> {
+        let ptr = with_image_surface(path, |surface| unsafe {
+            mouse::SDL_CreateColorCursor(surface, hot_x, hot_y)
+        })?;
+
+        if ptr.is_null() {
+            return Err(Error::sdl("Failed to create cursor"));
+        }
+
+        Ok(Cursor {
+            cursor: ptr,
+            _sdl: Arc::clone(sdl),
+        })
+    }
+
+    pub fn get_cursor(&self) -> *mut mouse::SDL_Cursor {
+        self.cursor
+    }
+}
+
+impl Drop for Cursor {
+    fn drop(&mut self) {
+        unsafe {
+            mouse::SDL_DestroyCursor(self.cursor);
+        }
+    }
+}
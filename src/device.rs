@@ -1,76 +1,366 @@
-use std::sync::{Arc, Mutex};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex, TryLockError, Weak,
+};
 
 use crate::{
     buffer::{
         gpu_buffer_type_to_sdl, gpu_transfer_usage_to_sdl, GPUBuffer, GPUBufferType,
         GPUTransferBuffer, GPUTransferUsage,
     },
-    command_buffer::CommandBuffer,
+    capture::{is_capturable_format, GPUFrameCapture},
+    command_buffer::{CommandBuffer, GPUBlitInfo, GPUBlitRegion},
     copypass::GPUCopypass,
+    error::{get_sdl_error_msg, Error},
+    event::Event,
     math::{Color, Rect},
     pipeline::{
         gpu_color_blend_factor_to_sdl, gpu_color_blend_op_to_sdl, gpu_vertex_element_to_sdl,
         GPUColorComponent, GPUGraphicsPipeline, GPUGraphicsPipelineCreateInfo,
     },
+    readback::GPUReadback,
     renderpass::GPURenderpass,
     shader::{GPUShader, GPUShaderType},
-    texture::{GPUTexture, GPUTextureAccess, GPUTextureCreateInfo, GPUTextureFormat},
+    texture::{
+        GPUTexture, GPUTextureAccess, GPUTextureCreateInfo, GPUTextureFormat, GPUTextureRegion,
+    },
     texture_utils::{
-        gpu_texture_access_to_sdl, gpu_texture_format_to_sdl, sdl_to_gpu_texture_format,
+        gpu_texture_access_to_sdl, gpu_texture_format_to_byte_size, gpu_texture_format_to_sdl,
+        sdl_to_gpu_texture_format,
     },
     window::Window,
 };
 use image::ImageReader;
-use sdl3_sys::{
-    error,
-    gpu::{
-        self, SDL_GPUBufferCreateInfo, SDL_GPUColorComponentFlags, SDL_GPUColorTargetInfo,
-        SDL_GPUSamplerCreateInfo, SDL_GPUTextureCreateInfo, SDL_GPUTransferBufferCreateInfo,
-    },
-    video,
+use sdl3_sys::gpu::{
+    self, SDL_GPUBufferCreateInfo, SDL_GPUColorComponentFlags, SDL_GPUColorTargetInfo,
+    SDL_GPUSamplerCreateInfo, SDL_GPUTextureCreateInfo, SDL_GPUTransferBufferCreateInfo,
 };
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(dead_code)]
+pub enum GPUPresentMode {
+    Vsync,
+    Immediate,
+    Mailbox,
+}
+
+pub fn gpu_present_mode_to_sdl(mode: GPUPresentMode) -> gpu::SDL_GPUPresentMode {
+    match mode {
+        GPUPresentMode::Vsync => gpu::SDL_GPU_PRESENTMODE_VSYNC,
+        GPUPresentMode::Immediate => gpu::SDL_GPU_PRESENTMODE_IMMEDIATE,
+        GPUPresentMode::Mailbox => gpu::SDL_GPU_PRESENTMODE_MAILBOX,
+    }
+}
+
+/// New pixel size of a claimed window, reported by `GPUDevice::handle_event`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GPUResize {
+    pub window_id: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// A texture that follows the pixel size of a window, see
+/// `GPUDevice::create_swapchain_sized_texture`.
+#[derive(Debug)]
+struct SwapchainSizedTexture {
+    window_id: u32,
+    texture: Weak<Mutex<GPUTexture>>,
+    format: GPUTextureFormat,
+    access: GPUTextureAccess,
+    scale: f32,
+    /// Window pixel size still to be applied, set while the texture was locked.
+    pending: Option<(u32, u32)>,
+}
+
+fn scaled_size(width: u32, height: u32, scale: f32) -> (u32, u32) {
+    let width = ((width as f32 * scale) as u32).max(1);
+    let height = ((height as f32 * scale) as u32).max(1);
+
+    (width, height)
+}
+
 #[derive(Debug)]
 #[allow(dead_code)]
 pub struct GPUDevice {
-    window: Arc<Window>,
+    windows: Mutex<Vec<Arc<Window>>>,
+    swapchain_sized_textures: Mutex<Vec<SwapchainSizedTexture>>,
+    /// Set while some swapchain-sized texture still waits for a resize.
+    resize_pending: AtomicBool,
     pub device: *mut gpu::SDL_GPUDevice,
 }
 
 #[allow(dead_code)]
 impl GPUDevice {
-    pub fn new(window: &Arc<Window>) -> Result<Arc<Self>, String> {
+    pub fn new(window: &Arc<Window>) -> Result<Arc<Self>, Error> {
+        let device = Self::new_headless()?;
+        device.claim_window(window)?;
+
+        Ok(device)
+    }
+
+    /// Create a device that renders offscreen only, without claiming any window.
+    /// Swapchain APIs return an error until a window is claimed.
+    pub fn new_headless() -> Result<Arc<Self>, Error> {
         let device = unsafe {
             gpu::SDL_CreateGPUDevice(gpu::SDL_GPU_SHADERFORMAT_SPIRV, true, std::ptr::null())
         };
 
         if device.is_null() {
-            return Err("Failed to create GPU device: ".to_string() + &Self::get_sdl_error_msg());
+            return Err(Error::DeviceCreation(get_sdl_error_msg()));
+        }
+
+        Ok(Arc::new(GPUDevice {
+            windows: Mutex::new(Vec::new()),
+            swapchain_sized_textures: Mutex::new(Vec::new()),
+            resize_pending: AtomicBool::new(false),
+            device,
+        }))
+    }
+
+    pub fn is_headless(&self) -> bool {
+        self.windows.lock().unwrap().is_empty()
+    }
+
+    /// Claim an additional window so swapchain textures can be acquired for it.
+    /// The first claimed window is the one used by `acquire_swapchain`.
+    pub fn claim_window(&self, window: &Arc<Window>) -> Result<(), Error> {
+        let mut windows = self.windows.lock().unwrap();
+        if windows
+            .iter()
+            .any(|w| w.get_window() == window.get_window())
+        {
+            return Err(Error::InvalidUsage(
+                "Window is already claimed by this GPU device".to_string(),
+            ));
         }
 
-        let res = unsafe { gpu::SDL_ClaimWindowForGPUDevice(device, window.get_window()) };
+        let res = unsafe { gpu::SDL_ClaimWindowForGPUDevice(self.device, window.get_window()) };
         if !res {
-            unsafe {
-                gpu::SDL_DestroyGPUDevice(device);
+            return Err(Error::sdl("Failed to claim window for GPU device"));
+        }
+
+        windows.push(Arc::clone(window));
+
+        Ok(())
+    }
+
+    /// Release a previously claimed window. Wait for any work presenting to it first.
+    pub fn release_window(&self, window: &Window) -> Result<(), Error> {
+        let mut windows = self.windows.lock().unwrap();
+        let index = windows
+            .iter()
+            .position(|w| w.get_window() == window.get_window());
+
+        let index = match index {
+            Some(index) => index,
+            None => {
+                return Err(Error::InvalidUsage(
+                    "Window is not claimed by this GPU device".to_string(),
+                ))
             }
+        };
 
-            return Err(
-                "Failed to claim window for GPU device: ".to_string() + &Self::get_sdl_error_msg()
-            );
+        unsafe {
+            gpu::SDL_ReleaseWindowFromGPUDevice(self.device, window.get_window());
         }
 
-        Ok(Arc::new(GPUDevice {
-            window: Arc::clone(window),
-            device,
-        }))
+        windows.remove(index);
+
+        let window_id = window.id();
+        self.swapchain_sized_textures
+            .lock()
+            .unwrap()
+            .retain(|entry| entry.window_id != window_id);
+
+        Ok(())
+    }
+
+    pub fn claimed_windows(&self) -> Vec<Arc<Window>> {
+        self.windows.lock().unwrap().clone()
+    }
+
+    fn primary_window(&self) -> Result<Arc<Window>, Error> {
+        match self.windows.lock().unwrap().first() {
+            Some(window) => Ok(Arc::clone(window)),
+            None => Err(Error::InvalidUsage(
+                "GPU device has no claimed window and no swapchain".to_string(),
+            )),
+        }
+    }
+
+    fn check_claimed(&self, window: &Window) -> Result<(), Error> {
+        let windows = self.windows.lock().unwrap();
+        if !windows
+            .iter()
+            .any(|w| w.get_window() == window.get_window())
+        {
+            return Err(Error::InvalidUsage(
+                "Window is not claimed by this GPU device".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Feed window events to the device. When a claimed window changes pixel
+    /// size, every swapchain-sized texture of that window is recreated at the
+    /// new size and the resize is returned so viewports can be updated too.
+    ///
+    /// A texture that is locked while its window resizes keeps its old size and
+    /// is recreated by a later call once the lock is released, see
+    /// `update_swapchain_sized_textures`.
+    pub fn handle_event(self: &Arc<Self>, event: &Event) -> Result<Option<GPUResize>, Error> {
+        let (window_id, width, height) = match event {
+            Event::WindowPixelSizeChanged {
+                window_id,
+                width,
+                height,
+            } => (*window_id, *width, *height),
+            _ => {
+                self.update_swapchain_sized_textures()?;
+                return Ok(None);
+            }
+        };
+
+        let claimed = self
+            .windows
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|w| w.id() == window_id);
+
+        if !claimed || width <= 0 || height <= 0 {
+            return Ok(None);
+        }
+
+        let resize = GPUResize {
+            window_id,
+            width: width as u32,
+            height: height as u32,
+        };
+
+        self.resize_swapchain_sized_textures(&resize)?;
+
+        Ok(Some(resize))
+    }
+
+    fn resize_swapchain_sized_textures(self: &Arc<Self>, resize: &GPUResize) -> Result<(), Error> {
+        {
+            let mut entries = self.swapchain_sized_textures.lock().unwrap();
+            for entry in entries
+                .iter_mut()
+                .filter(|e| e.window_id == resize.window_id)
+            {
+                entry.pending = Some((resize.width, resize.height));
+                self.resize_pending.store(true, Ordering::Relaxed);
+            }
+        }
+
+        self.update_swapchain_sized_textures()
+    }
+
+    /// Recreate swapchain-sized textures whose window was resized while they
+    /// were locked. Textures that are still locked stay pending, so callers that
+    /// hold a texture across frames should release it and call this again.
+    pub fn update_swapchain_sized_textures(self: &Arc<Self>) -> Result<(), Error> {
+        if !self.resize_pending.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        let mut entries = self.swapchain_sized_textures.lock().unwrap();
+        entries.retain(|entry| entry.texture.strong_count() > 0);
+
+        for entry in entries.iter_mut() {
+            let (width, height) = match entry.pending {
+                Some(size) => size,
+                None => continue,
+            };
+
+            let texture = match entry.texture.upgrade() {
+                Some(texture) => texture,
+                None => continue,
+            };
+
+            let mut texture = match texture.try_lock() {
+                Ok(texture) => texture,
+                Err(TryLockError::WouldBlock) => continue,
+                Err(TryLockError::Poisoned(err)) => err.into_inner(),
+            };
+
+            let (width, height) = scaled_size(width, height, entry.scale);
+            let info = GPUTextureCreateInfo {
+                data: Vec::new(),
+                width,
+                height,
+                format: entry.format,
+                access: entry.access,
+            };
+
+            // Swap the new texture into the shared handle so existing users see
+            // it, the old one is released when `replacement` is dropped. SDL
+            // defers the release until in-flight command buffers are done with it.
+            let replacement = self.create_texture(&info)?;
+            std::mem::swap(&mut *texture, &mut *replacement.lock().unwrap());
+            entry.pending = None;
+        }
+
+        let pending = entries.iter().any(|entry| entry.pending.is_some());
+        self.resize_pending.store(pending, Ordering::Relaxed);
+
+        Ok(())
     }
 
-    pub fn acquire_command_buffer(self: &Arc<Self>) -> Result<CommandBuffer, String> {
+    /// Create a texture sized to `scale` times the pixel size of `window` that is
+    /// recreated automatically by `handle_event` whenever the window is resized.
+    /// Useful for offscreen render targets, depth buffers and post-process chains.
+    pub fn create_swapchain_sized_texture(
+        self: &Arc<Self>,
+        window: &Window,
+        format: GPUTextureFormat,
+        access: GPUTextureAccess,
+        scale: f32,
+    ) -> Result<Arc<Mutex<GPUTexture>>, Error> {
+        self.check_claimed(window)?;
+
+        if scale.is_nan() || scale <= 0.0 {
+            return Err(Error::InvalidUsage(
+                "Texture scale must be greater than zero".to_string(),
+            ));
+        }
+
+        let size = window.pixel_size();
+        let (width, height) = scaled_size(size.w as u32, size.h as u32, scale);
+
+        let info = GPUTextureCreateInfo {
+            data: Vec::new(),
+            width,
+            height,
+            format,
+            access,
+        };
+
+        let texture = self.create_texture(&info)?;
+
+        self.swapchain_sized_textures
+            .lock()
+            .unwrap()
+            .push(SwapchainSizedTexture {
+                window_id: window.id(),
+                texture: Arc::downgrade(&texture),
+                format,
+                access,
+                scale,
+                pending: None,
+            });
+
+        Ok(texture)
+    }
+
+    pub fn acquire_command_buffer(self: &Arc<Self>) -> Result<CommandBuffer, Error> {
         let command_buffer = unsafe { gpu::SDL_AcquireGPUCommandBuffer(self.device) };
         if command_buffer.is_null() {
-            return Err(
-                "Failed to acquire command buffer: ".to_string() + &Self::get_sdl_error_msg()
-            );
+            return Err(Error::sdl("Failed to acquire command buffer"));
         }
 
         Ok(CommandBuffer {
@@ -81,41 +371,47 @@ impl GPUDevice {
         })
     }
 
+    /// Acquire the swapchain texture of the primary (first claimed) window.
     pub fn acquire_swapchain(
         &self,
         command_buffer: &mut CommandBuffer,
-    ) -> Result<Option<Arc<GPUTexture>>, String> {
+    ) -> Result<Option<Arc<GPUTexture>>, Error> {
+        let window = self.primary_window()?;
+        self.acquire_swapchain_for(command_buffer, &window)
+    }
+
+    pub fn acquire_swapchain_for(
+        &self,
+        command_buffer: &mut CommandBuffer,
+        window: &Window,
+    ) -> Result<Option<Arc<GPUTexture>>, Error> {
+        self.check_claimed(window)?;
+
         let mut ptr_to_texture: *mut gpu::SDL_GPUTexture = std::ptr::null_mut();
         let ptr_to_texture_ptr: *mut *mut gpu::SDL_GPUTexture = &mut ptr_to_texture;
-        let mut zero: u32 = 0;
-        let ptr_to_zero: *mut u32 = &mut zero;
+        let mut width: u32 = 0;
+        let mut height: u32 = 0;
         let res = unsafe {
             gpu::SDL_AcquireGPUSwapchainTexture(
                 command_buffer.command_buffer,
-                self.window.get_window(),
+                window.get_window(),
                 ptr_to_texture_ptr,
-                ptr_to_zero,
-                ptr_to_zero,
+                &mut width,
+                &mut height,
             )
         };
 
         if !res {
-            return Err(
-                "Failed to acquire swapchain texture: ".to_string() + &Self::get_sdl_error_msg()
-            );
+            return Err(Error::sdl("Failed to acquire swapchain texture"));
         }
 
         if ptr_to_texture.is_null() {
             return Ok(None);
         }
 
-        let mut width: i32 = 0;
-        let mut height: i32 = 0;
-        unsafe {
-            video::SDL_GetWindowSize(self.window.get_window(), &mut width, &mut height);
-        }
-
-        let size = Rect::new(0, 0, width, height);
+        // The swapchain is sized in pixels, which differs from the window size
+        // in points on high-DPI displays.
+        let size = Rect::new(0, 0, width as i32, height as i32);
 
         command_buffer.has_swapchain = true;
         Ok(Some(Arc::new(GPUTexture {
@@ -124,16 +420,72 @@ impl GPUDevice {
             sampler: std::ptr::null_mut(),
             should_destroy: false,
             size,
-            format: GPUTextureFormat::R8G8B8A8,
+            format: self.get_swapchain_format_for(window)?,
             access: GPUTextureAccess::RenderTarget,
+            layer_count: 1,
+            num_levels: 1,
         })))
     }
 
-    pub fn get_swapchain_format(&self) -> GPUTextureFormat {
+    pub fn get_swapchain_format(&self) -> Result<GPUTextureFormat, Error> {
+        let window = self.primary_window()?;
+        self.get_swapchain_format_for(&window)
+    }
+
+    pub fn get_swapchain_format_for(&self, window: &Window) -> Result<GPUTextureFormat, Error> {
+        self.check_claimed(window)?;
+
         let fmt =
-            unsafe { gpu::SDL_GetGPUSwapchainTextureFormat(self.device, self.window.get_window()) };
+            unsafe { gpu::SDL_GetGPUSwapchainTextureFormat(self.device, window.get_window()) };
+
+        Ok(sdl_to_gpu_texture_format(fmt))
+    }
+
+    pub fn supports_present_mode(&self, window: &Window, mode: GPUPresentMode) -> bool {
+        unsafe {
+            gpu::SDL_WindowSupportsGPUPresentMode(
+                self.device,
+                window.get_window(),
+                gpu_present_mode_to_sdl(mode),
+            )
+        }
+    }
+
+    /// Set the present mode of the primary window, see `set_present_mode_for`.
+    pub fn set_present_mode(&self, mode: GPUPresentMode) -> Result<GPUPresentMode, Error> {
+        let window = self.primary_window()?;
+        self.set_present_mode_for(&window, mode)
+    }
+
+    /// Set the present mode of a claimed window. Unsupported modes fall back to
+    /// `Vsync`, which is always available; the mode actually applied is returned.
+    pub fn set_present_mode_for(
+        &self,
+        window: &Window,
+        mode: GPUPresentMode,
+    ) -> Result<GPUPresentMode, Error> {
+        self.check_claimed(window)?;
+
+        let mode = if self.supports_present_mode(window, mode) {
+            mode
+        } else {
+            GPUPresentMode::Vsync
+        };
+
+        let res = unsafe {
+            gpu::SDL_SetGPUSwapchainParameters(
+                self.device,
+                window.get_window(),
+                gpu::SDL_GPU_SWAPCHAINCOMPOSITION_SDR,
+                gpu_present_mode_to_sdl(mode),
+            )
+        };
+
+        if !res {
+            return Err(Error::sdl("Failed to set swapchain parameters"));
+        }
 
-        sdl_to_gpu_texture_format(fmt)
+        Ok(mode)
     }
 
     pub fn begin_renderpass(
@@ -141,7 +493,7 @@ impl GPUDevice {
         command_buffer: &mut CommandBuffer,
         color: &Color,
         texture: &GPUTexture,
-    ) -> Result<Arc<Mutex<GPURenderpass>>, String> {
+    ) -> Result<Arc<Mutex<GPURenderpass>>, Error> {
         let mut color_target: SDL_GPUColorTargetInfo = unsafe { std::mem::zeroed() };
         color_target.texture = texture.texture;
         color_target.clear_color.r = color.r;
@@ -162,7 +514,7 @@ impl GPUDevice {
         };
 
         if res.is_null() {
-            return Err("Failed to begin renderpass".to_string());
+            return Err(Error::sdl("Failed to begin renderpass"));
         }
 
         Ok(Arc::new(Mutex::new(GPURenderpass {
@@ -174,11 +526,11 @@ impl GPUDevice {
     pub fn begin_copypass(
         &self,
         command_buffer: &mut CommandBuffer,
-    ) -> Result<Arc<Mutex<GPUCopypass>>, String> {
+    ) -> Result<Arc<Mutex<GPUCopypass>>, Error> {
         let copypass = unsafe { gpu::SDL_BeginGPUCopyPass(command_buffer.command_buffer) };
 
         if copypass.is_null() {
-            return Err("Failed to begin copypass".to_string());
+            return Err(Error::sdl("Failed to begin copypass"));
         }
 
         Ok(Arc::new(Mutex::new(GPUCopypass {
@@ -190,7 +542,7 @@ impl GPUDevice {
     pub fn create_texture(
         self: &Arc<Self>,
         info: &GPUTextureCreateInfo,
-    ) -> Result<Arc<Mutex<GPUTexture>>, String> {
+    ) -> Result<Arc<Mutex<GPUTexture>>, Error> {
         let mut create_info: SDL_GPUTextureCreateInfo = unsafe { std::mem::zeroed() };
         create_info.format = gpu_texture_format_to_sdl(info.format);
         create_info.usage = gpu_texture_access_to_sdl(info.access);
@@ -201,20 +553,7 @@ impl GPUDevice {
 
         let texture = unsafe { gpu::SDL_CreateGPUTexture(self.device, &create_info) };
         if texture.is_null() {
-            return Err("Failed to create texture".to_string());
-        }
-
-        let transfer_buffer = self.create_transfer_buffer(
-            (info.width * info.height * 4) as usize,
-            GPUTransferUsage::Upload,
-        );
-
-        if let Err(err) = transfer_buffer {
-            unsafe {
-                gpu::SDL_ReleaseGPUTexture(self.device, texture);
-            }
-
-            return Err(err);
+            return Err(Error::sdl("Failed to create texture"));
         }
 
         let mut sampler_create_info: SDL_GPUSamplerCreateInfo = unsafe { std::mem::zeroed() };
@@ -238,7 +577,7 @@ impl GPUDevice {
                 gpu::SDL_ReleaseGPUTexture(self.device, texture);
             }
 
-            return Err("Failed to create sampler".to_string());
+            return Err(Error::sdl("Failed to create sampler"));
         }
 
         let res = Arc::new(Mutex::new(GPUTexture {
@@ -249,16 +588,21 @@ impl GPUDevice {
             size: Rect::new(0, 0, info.width as i32, info.height as i32),
             format: info.format,
             access: info.access,
+            layer_count: 1,
+            num_levels: 1,
         }));
 
-        let transfer_buffer = transfer_buffer.unwrap();
-        let transfer_res = transfer_buffer.write_texture(self, &res, &info.data);
-        if let Err(_err) = transfer_res {
-            unsafe {
-                gpu::SDL_ReleaseGPUTexture(self.device, texture);
-            }
+        // Textures without initial data (render targets, copy destinations) skip the upload.
+        if info.data.is_empty() {
+            return Ok(res);
+        }
 
-            return Err("Failed to write texture".to_string());
+        let transfer_buffer =
+            self.create_transfer_buffer(info.data.len(), GPUTransferUsage::Upload)?;
+
+        let transfer_res = transfer_buffer.write_texture(self, &res, &info.data);
+        if let Err(err) = transfer_res {
+            return Err(err);
         }
 
         Ok(res)
@@ -267,37 +611,40 @@ impl GPUDevice {
     pub fn create_texture_from_file(
         self: &Arc<Self>,
         path: &str,
-    ) -> Result<Arc<Mutex<GPUTexture>>, String> {
+    ) -> Result<Arc<Mutex<GPUTexture>>, Error> {
         let img = ImageReader::open(path);
         if let Err(err) = img {
-            return Err(format!("Failed to open image: {:?}", err));
+            return Err(Error::Image(format!("Failed to open image: {:?}", err)));
         }
 
         let img = img.unwrap().decode();
         if let Err(err) = img {
-            return Err(format!("Failed to decode image: {:?}", err));
+            return Err(Error::Image(format!("Failed to decode image: {:?}", err)));
         }
 
         let img = img.unwrap();
-        let data = img.as_bytes();
+        match img.color() {
+            image::ColorType::Rgb8 | image::ColorType::Rgba8 => {}
+            _ => {
+                return Err(Error::UnsupportedFormat(
+                    "Unsupported texture format".to_string(),
+                ))
+            }
+        }
+
+        // GPU textures have no 3-channel format, so expand RGB to RGBA.
+        let img = img.into_rgba8();
         let width = img.width();
         let height = img.height();
-        let format = img.color();
 
-        let mut info = GPUTextureCreateInfo {
-            data: data.to_vec(),
+        let info = GPUTextureCreateInfo {
+            data: img.into_raw(),
             width,
             height,
             format: GPUTextureFormat::R8G8B8A8,
             access: GPUTextureAccess::Sampler,
         };
 
-        info.format = match format {
-            image::ColorType::Rgb8 => GPUTextureFormat::R8G8B8A8,
-            image::ColorType::Rgba8 => GPUTextureFormat::R8G8B8A8,
-            _ => return Err("Unsupported texture format".to_string()),
-        };
-
         let texture = self.create_texture(&info);
         if let Err(err) = texture {
             return Err(err);
@@ -308,11 +655,29 @@ impl GPUDevice {
         Ok(texture)
     }
 
+    /// Load a file dropped on `window` as a texture. Returns `None` when `event`
+    /// is not a file drop on that window, and an error if the file is not an
+    /// image that `create_texture_from_file` supports.
+    pub fn create_texture_from_drop(
+        self: &Arc<Self>,
+        window: &Window,
+        event: &Event,
+    ) -> Result<Option<Arc<Mutex<GPUTexture>>>, Error> {
+        let path = match window.dropped_file(event) {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+
+        let texture = self.create_texture_from_file(path)?;
+
+        Ok(Some(texture))
+    }
+
     pub fn create_buffer(
         self: &Arc<Self>,
         size: usize,
         usage: GPUBufferType,
-    ) -> Result<Arc<Mutex<GPUBuffer>>, String> {
+    ) -> Result<Arc<Mutex<GPUBuffer>>, Error> {
         let mut info: SDL_GPUBufferCreateInfo = unsafe { std::mem::zeroed() };
         info.size = size as u32;
         info.usage = gpu_buffer_type_to_sdl(usage);
@@ -320,7 +685,7 @@ impl GPUDevice {
 
         let buffer = unsafe { gpu::SDL_CreateGPUBuffer(self.device, &info) };
         if buffer.is_null() {
-            return Err("Failed to create buffer".to_string());
+            return Err(Error::sdl("Failed to create buffer"));
         }
 
         Ok(Arc::new(Mutex::new(GPUBuffer {
@@ -334,7 +699,7 @@ impl GPUDevice {
         &self,
         size: usize,
         usage: GPUTransferUsage,
-    ) -> Result<Arc<GPUTransferBuffer>, String> {
+    ) -> Result<Arc<GPUTransferBuffer>, Error> {
         let mut info: SDL_GPUTransferBufferCreateInfo = unsafe { std::mem::zeroed() };
         info.size = size as u32;
         info.usage = gpu_transfer_usage_to_sdl(usage);
@@ -342,7 +707,7 @@ impl GPUDevice {
 
         let buffer = unsafe { gpu::SDL_CreateGPUTransferBuffer(self.device, &info) };
         if buffer.is_null() {
-            return Err("Failed to create transfer buffer".to_string());
+            return Err(Error::sdl("Failed to create transfer buffer"));
         }
 
         Ok(Arc::new(GPUTransferBuffer {
@@ -353,6 +718,109 @@ impl GPUDevice {
         }))
     }
 
+    /// Start downloading the texture into a freshly allocated transfer buffer.
+    /// Poll or wait on the returned handle to get the bytes.
+    pub fn read_texture_async(
+        self: &Arc<Self>,
+        texture: &Arc<Mutex<GPUTexture>>,
+    ) -> Result<GPUReadback, Error> {
+        let size = {
+            let texture = texture.lock().unwrap();
+            let byte_size = gpu_texture_format_to_byte_size(texture.format) as usize;
+
+            texture.size.w as usize * texture.size.h as usize * byte_size
+        };
+
+        let transfer_buffer = self.create_transfer_buffer(size, GPUTransferUsage::Download)?;
+        transfer_buffer.read_texture_async(self, texture)
+    }
+
+    /// Start downloading the buffer into a freshly allocated transfer buffer.
+    /// Poll or wait on the returned handle to get the bytes.
+    pub fn read_buffer_async(
+        self: &Arc<Self>,
+        buffer: &Arc<Mutex<GPUBuffer>>,
+    ) -> Result<GPUReadback, Error> {
+        let size = buffer.lock().unwrap().size as usize;
+
+        let transfer_buffer = self.create_transfer_buffer(size, GPUTransferUsage::Download)?;
+        transfer_buffer.read_buffer_async(self, buffer)
+    }
+
+    /// Download `frame` and submit the command buffer. Call this in place of
+    /// `submit` once every pass rendering into the frame has ended; the returned
+    /// capture can then be saved.
+    ///
+    /// Swapchain textures can only be rendered to, so `frame` has to be an
+    /// offscreen `SampledRenderTarget` texture. When `present_to` is given
+    /// (usually the swapchain texture) the frame is blitted to it first, so the
+    /// captured frame is also the one shown.
+    pub fn capture_frame(
+        self: &Arc<Self>,
+        command_buffer: &mut CommandBuffer,
+        frame: &GPUTexture,
+        present_to: Option<&GPUTexture>,
+    ) -> Result<GPUFrameCapture, Error> {
+        if !matches!(frame.access, GPUTextureAccess::SampledRenderTarget) {
+            return Err(Error::InvalidUsage(
+                "Captured frames must be SampledRenderTarget textures".to_string(),
+            ));
+        }
+
+        if !is_capturable_format(frame.format) {
+            return Err(Error::UnsupportedFormat(format!(
+                "Unsupported frame format for capture: {:?}",
+                frame.format
+            )));
+        }
+
+        let width = frame.size.w as u32;
+        let height = frame.size.h as u32;
+        let size = width
+            .checked_mul(height)
+            .and_then(|size| size.checked_mul(gpu_texture_format_to_byte_size(frame.format)));
+
+        let size = match size {
+            Some(size) => size as usize,
+            None => {
+                return Err(Error::SizeMismatch(
+                    "Frame is too large to capture".to_string(),
+                ))
+            }
+        };
+
+        let transfer_buffer = self.create_transfer_buffer(size, GPUTransferUsage::Download)?;
+
+        if let Some(target) = present_to {
+            command_buffer.blit(
+                frame,
+                &GPUBlitRegion::full(frame),
+                target,
+                &GPUBlitRegion::full(target),
+                &GPUBlitInfo::default(),
+            )?;
+        }
+
+        {
+            let region = GPUTextureRegion::new(0, 0, width, height);
+
+            let copypass = self.begin_copypass(command_buffer)?;
+            let mut copypass = copypass.lock().unwrap();
+
+            copypass.download_from_texture(frame, &region, &transfer_buffer, 0)?;
+            copypass.end();
+        }
+
+        let fence = command_buffer.submit_and_acquire_fence()?;
+
+        Ok(GPUFrameCapture::new(
+            GPUReadback::new(fence, transfer_buffer, size),
+            width,
+            height,
+            frame.format,
+        ))
+    }
+
     pub fn create_shader(
         self: &Arc<Self>,
         shader_type: GPUShaderType,
@@ -360,7 +828,7 @@ impl GPUDevice {
         num_samplers: u32,
         num_uniform_buffers: u32,
         num_storage_buffers: u32,
-    ) -> Result<Arc<Mutex<GPUShader>>, String> {
+    ) -> Result<Arc<Mutex<GPUShader>>, Error> {
         let mut shader_create_info: gpu::SDL_GPUShaderCreateInfo = unsafe { std::mem::zeroed() };
         shader_create_info.code = source.as_ptr() as *const u8;
         shader_create_info.code_size = source.len() as usize;
@@ -378,7 +846,7 @@ impl GPUDevice {
 
         let shader = unsafe { gpu::SDL_CreateGPUShader(self.device, &shader_create_info) };
         if shader.is_null() {
-            return Err("Failed to create shader".to_string());
+            return Err(Error::sdl("Failed to create shader"));
         }
 
         Ok(Arc::new(Mutex::new(GPUShader {
@@ -391,7 +859,7 @@ impl GPUDevice {
     pub fn create_graphics_pipeline(
         self: &Arc<Self>,
         info: &GPUGraphicsPipelineCreateInfo,
-    ) -> Result<Arc<Mutex<GPUGraphicsPipeline>>, String> {
+    ) -> Result<Arc<Mutex<GPUGraphicsPipeline>>, Error> {
         let mut pipeline_create_info: gpu::SDL_GPUGraphicsPipelineCreateInfo =
             unsafe { std::mem::zeroed() };
 
@@ -493,7 +961,7 @@ impl GPUDevice {
             unsafe { gpu::SDL_CreateGPUGraphicsPipeline(self.device, &pipeline_create_info) };
 
         if pipeline.is_null() {
-            return Err("Failed to create graphics pipeline".to_string());
+            return Err(Error::sdl("Failed to create graphics pipeline"));
         }
 
         Ok(Arc::new(Mutex::new(GPUGraphicsPipeline {
@@ -501,18 +969,15 @@ impl GPUDevice {
             pipeline,
         })))
     }
-
-    fn get_sdl_error_msg() -> String {
-        let cstr = unsafe { std::ffi::CStr::from_ptr(error::SDL_GetError()) };
-        let str_slice = cstr.to_str().unwrap();
-
-        str_slice.to_string()
-    }
 }
 
 impl Drop for GPUDevice {
     fn drop(&mut self) {
         unsafe {
+            for window in self.windows.lock().unwrap().iter() {
+                gpu::SDL_ReleaseWindowFromGPUDevice(self.device, window.get_window());
+            }
+
             gpu::SDL_DestroyGPUDevice(self.device);
         }
     }
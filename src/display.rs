@@ -0,0 +1,169 @@
+use sdl3_sys::{rect, stdinc, video};
+
+use crate::{error::Error, event::cstr_to_string, math::Rect, sdl::Sdl};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisplayMode {
+    pub display_id: u32,
+    pub width: i32,
+    pub height: i32,
+    pub pixel_density: f32,
+    /// Refresh rate in Hz, 0.0 if unknown.
+    pub refresh_rate: f32,
+}
+
+pub fn sdl_to_display_mode(mode: &video::SDL_DisplayMode) -> DisplayMode {
+    DisplayMode {
+        display_id: mode.displayID,
+        width: mode.w,
+        height: mode.h,
+        pixel_density: mode.pixel_density,
+        refresh_rate: mode.refresh_rate,
+    }
+}
+
+fn sdl_to_rect(rect: &rect::SDL_Rect) -> Rect {
+    Rect::new(rect.x, rect.y, rect.w, rect.h)
+}
+
+/// A connected monitor. Display ids stay valid until the display is unplugged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Display {
+    pub id: u32,
+}
+
+#[allow(dead_code)]
+impl Display {
+    /// Takes the `Sdl` context so displays cannot be queried before video is
+    /// initialized.
+    pub fn all(_sdl: &Sdl) -> Result<Vec<Display>, Error> {
+        let mut count: i32 = 0;
+        let ids = unsafe { video::SDL_GetDisplays(&mut count) };
+        if ids.is_null() {
+            return Err(Error::sdl("Failed to get displays"));
+        }
+
+        let res = unsafe { std::slice::from_raw_parts(ids, count as usize) }
+            .iter()
+            .map(|id| Display { id: *id })
+            .collect();
+
+        unsafe {
+            stdinc::SDL_free(ids as *mut std::ffi::c_void);
+        }
+
+        Ok(res)
+    }
+
+    pub fn primary(_sdl: &Sdl) -> Result<Display, Error> {
+        let id = unsafe { video::SDL_GetPrimaryDisplay() };
+        if id == 0 {
+            return Err(Error::sdl("Failed to get primary display"));
+        }
+
+        Ok(Display { id })
+    }
+
+    pub fn name(&self) -> String {
+        let name = unsafe { video::SDL_GetDisplayName(self.id) };
+
+        cstr_to_string(name)
+    }
+
+    /// Desktop area covered by the display, in points.
+    pub fn bounds(&self) -> Result<Rect, Error> {
+        let mut bounds: rect::SDL_Rect = unsafe { std::mem::zeroed() };
+        let res = unsafe { video::SDL_GetDisplayBounds(self.id, &mut bounds) };
+        if !res {
+            return Err(Error::sdl("Failed to get display bounds"));
+        }
+
+        Ok(sdl_to_rect(&bounds))
+    }
+
+    /// Like `bounds`, minus taskbars, docks and menu bars.
+    pub fn usable_bounds(&self) -> Result<Rect, Error> {
+        let mut bounds: rect::SDL_Rect = unsafe { std::mem::zeroed() };
+        let res = unsafe { video::SDL_GetDisplayUsableBounds(self.id, &mut bounds) };
+        if !res {
+            return Err(Error::sdl("Failed to get display usable bounds"));
+        }
+
+        Ok(sdl_to_rect(&bounds))
+    }
+
+    pub fn content_scale(&self) -> Result<f32, Error> {
+        let scale = unsafe { video::SDL_GetDisplayContentScale(self.id) };
+        if scale == 0.0 {
+            return Err(Error::sdl("Failed to get display content scale"));
+        }
+
+        Ok(scale)
+    }
+
+    /// The mode the desktop uses on this display.
+    pub fn desktop_mode(&self) -> Result<DisplayMode, Error> {
+        let mode = unsafe { video::SDL_GetDesktopDisplayMode(self.id) };
+        if mode.is_null() {
+            return Err(Error::sdl("Failed to get desktop display mode"));
+        }
+
+        Ok(sdl_to_display_mode(unsafe { &*mode }))
+    }
+
+    /// The mode currently in use, which differs from the desktop mode while a
+    /// window is in exclusive fullscreen.
+    pub fn current_mode(&self) -> Result<DisplayMode, Error> {
+        let mode = unsafe { video::SDL_GetCurrentDisplayMode(self.id) };
+        if mode.is_null() {
+            return Err(Error::sdl("Failed to get current display mode"));
+        }
+
+        Ok(sdl_to_display_mode(unsafe { &*mode }))
+    }
+
+    pub fn refresh_rate(&self) -> Result<f32, Error> {
+        Ok(self.current_mode()?.refresh_rate)
+    }
+
+    /// Modes available for exclusive fullscreen, largest and fastest first.
+    pub fn fullscreen_modes(&self) -> Result<Vec<DisplayMode>, Error> {
+        let mut count: i32 = 0;
+        let modes = unsafe { video::SDL_GetFullscreenDisplayModes(self.id, &mut count) };
+        if modes.is_null() {
+            return Err(Error::sdl("Failed to get fullscreen display modes"));
+        }
+
+        let res = unsafe { std::slice::from_raw_parts(modes, count as usize) }
+            .iter()
+            .map(|mode| sdl_to_display_mode(unsafe { &**mode }))
+            .collect();
+
+        unsafe {
+            stdinc::SDL_free(modes as *mut std::ffi::c_void);
+        }
+
+        Ok(res)
+    }
+}
+
+/// Resolve a `DisplayMode` back to the SDL mode of its display.
+pub fn display_mode_to_sdl(mode: &DisplayMode) -> Result<video::SDL_DisplayMode, Error> {
+    let mut res: video::SDL_DisplayMode = unsafe { std::mem::zeroed() };
+    let found = unsafe {
+        video::SDL_GetClosestFullscreenDisplayMode(
+            mode.display_id,
+            mode.width,
+            mode.height,
+            mode.refresh_rate,
+            mode.pixel_density > 1.0,
+            &mut res,
+        )
+    };
+
+    if !found {
+        return Err(Error::sdl("Display mode is not available"));
+    }
+
+    Ok(res)
+}
@@ -0,0 +1,54 @@
+use std::fmt;
+
+use sdl3_sys::error;
+
+/// Error type shared by the whole crate, not only the GPU wrappers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    DeviceCreation(String),
+    OutOfMemory(String),
+    UnsupportedFormat(String),
+    InvalidUsage(String),
+    SizeMismatch(String),
+    AlreadySubmitted,
+    AlreadyEnded,
+    Image(String),
+    Sdl(String),
+    Io(String),
+    Parse { line: usize, message: String },
+}
+
+impl Error {
+    /// Build an error from a failed SDL call, keeping the message from `SDL_GetError`.
+    pub fn sdl(context: &str) -> Self {
+        Error::Sdl(format!("{}: {}", context, get_sdl_error_msg()))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::DeviceCreation(msg) => write!(f, "Failed to create GPU device: {}", msg),
+            Error::OutOfMemory(msg) => write!(f, "Out of memory: {}", msg),
+            Error::UnsupportedFormat(msg) => write!(f, "Unsupported format: {}", msg),
+            Error::InvalidUsage(msg) => write!(f, "Invalid usage: {}", msg),
+            Error::SizeMismatch(msg) => write!(f, "Size mismatch: {}", msg),
+            Error::AlreadySubmitted => write!(f, "Command buffer is already used!"),
+            Error::AlreadyEnded => write!(f, "Pass is already ended!"),
+            Error::Image(msg) => write!(f, "Image error: {}", msg),
+            Error::Sdl(msg) => write!(f, "SDL error: {}", msg),
+            Error::Io(msg) => write!(f, "IO error: {}", msg),
+            Error::Parse { line, message } => {
+                write!(f, "Parse error at line {}: {}", line, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub fn get_sdl_error_msg() -> String {
+    let cstr = unsafe { std::ffi::CStr::from_ptr(error::SDL_GetError()) };
+
+    cstr.to_string_lossy().into_owned()
+}
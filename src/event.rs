@@ -0,0 +1,516 @@
+use std::{ffi::CStr, sync::Arc};
+
+use sdl3_sys::{
+    events::{self, SDL_Event},
+    keyboard, keycode, mouse, scancode,
+};
+
+use crate::{
+    gamepad::{sdl_to_gamepad_axis, sdl_to_gamepad_button, GamepadAxis, GamepadButton},
+    sdl::Sdl,
+};
+
+/// Physical key position, independent of keyboard layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Scancode(pub u32);
+
+#[allow(dead_code)]
+impl Scancode {
+    /// Look up a scancode by its SDL name, e.g. "W" or "Space".
+    pub fn from_name(name: &str) -> Option<Self> {
+        let cstr = std::ffi::CString::new(name).ok()?;
+        let code = unsafe { keyboard::SDL_GetScancodeFromName(cstr.as_ptr()) };
+
+        if code == scancode::SDL_SCANCODE_UNKNOWN {
+            return None;
+        }
+
+        Some(Scancode(code.0 as u32))
+    }
+
+    pub fn name(&self) -> String {
+        let name = unsafe { keyboard::SDL_GetScancodeName(scancode::SDL_Scancode(self.0 as _)) };
+
+        cstr_to_string(name)
+    }
+}
+
+/// Layout dependent key symbol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Keycode(pub u32);
+
+#[allow(dead_code)]
+impl Keycode {
+    /// Look up a keycode by its SDL name, e.g. "A" or "Escape".
+    pub fn from_name(name: &str) -> Option<Self> {
+        let cstr = std::ffi::CString::new(name).ok()?;
+        let code = unsafe { keyboard::SDL_GetKeyFromName(cstr.as_ptr()) };
+
+        if code == keycode::SDLK_UNKNOWN {
+            return None;
+        }
+
+        Some(Keycode(code as u32))
+    }
+
+    pub fn name(&self) -> String {
+        let name = unsafe { keyboard::SDL_GetKeyName(self.0 as keycode::SDL_Keycode) };
+
+        cstr_to_string(name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct KeyModifiers(pub u16);
+
+#[allow(dead_code)]
+impl KeyModifiers {
+    pub const NONE: KeyModifiers = KeyModifiers(0);
+    pub const LSHIFT: KeyModifiers = KeyModifiers(keycode::SDL_KMOD_LSHIFT);
+    pub const RSHIFT: KeyModifiers = KeyModifiers(keycode::SDL_KMOD_RSHIFT);
+    pub const LCTRL: KeyModifiers = KeyModifiers(keycode::SDL_KMOD_LCTRL);
+    pub const RCTRL: KeyModifiers = KeyModifiers(keycode::SDL_KMOD_RCTRL);
+    pub const LALT: KeyModifiers = KeyModifiers(keycode::SDL_KMOD_LALT);
+    pub const RALT: KeyModifiers = KeyModifiers(keycode::SDL_KMOD_RALT);
+    pub const LGUI: KeyModifiers = KeyModifiers(keycode::SDL_KMOD_LGUI);
+    pub const RGUI: KeyModifiers = KeyModifiers(keycode::SDL_KMOD_RGUI);
+
+    pub fn contains(&self, other: KeyModifiers) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn shift(&self) -> bool {
+        self.0 & (Self::LSHIFT.0 | Self::RSHIFT.0) != 0
+    }
+
+    pub fn ctrl(&self) -> bool {
+        self.0 & (Self::LCTRL.0 | Self::RCTRL.0) != 0
+    }
+
+    pub fn alt(&self) -> bool {
+        self.0 & (Self::LALT.0 | Self::RALT.0) != 0
+    }
+
+    pub fn gui(&self) -> bool {
+        self.0 & (Self::LGUI.0 | Self::RGUI.0) != 0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+    X1,
+    X2,
+    Other(u8),
+}
+
+pub fn sdl_to_mouse_button(button: u8) -> MouseButton {
+    match button as i32 {
+        mouse::SDL_BUTTON_LEFT => MouseButton::Left,
+        mouse::SDL_BUTTON_MIDDLE => MouseButton::Middle,
+        mouse::SDL_BUTTON_RIGHT => MouseButton::Right,
+        mouse::SDL_BUTTON_X1 => MouseButton::X1,
+        mouse::SDL_BUTTON_X2 => MouseButton::X2,
+        _ => MouseButton::Other(button),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    Quit,
+    WindowResized {
+        window_id: u32,
+        width: i32,
+        height: i32,
+    },
+    /// The drawable size in pixels changed, e.g. after a resize or when the
+    /// window moved to a display with a different pixel density.
+    WindowPixelSizeChanged {
+        window_id: u32,
+        width: i32,
+        height: i32,
+    },
+    WindowDisplayScaleChanged {
+        window_id: u32,
+    },
+    WindowMoved {
+        window_id: u32,
+        x: i32,
+        y: i32,
+    },
+    WindowFocusGained {
+        window_id: u32,
+    },
+    WindowFocusLost {
+        window_id: u32,
+    },
+    WindowCloseRequested {
+        window_id: u32,
+    },
+    KeyDown {
+        window_id: u32,
+        scancode: Scancode,
+        keycode: Keycode,
+        modifiers: KeyModifiers,
+        repeat: bool,
+    },
+    KeyUp {
+        window_id: u32,
+        scancode: Scancode,
+        keycode: Keycode,
+        modifiers: KeyModifiers,
+    },
+    MouseMotion {
+        window_id: u32,
+        x: f32,
+        y: f32,
+        xrel: f32,
+        yrel: f32,
+    },
+    MouseButtonDown {
+        window_id: u32,
+        button: MouseButton,
+        clicks: u8,
+        x: f32,
+        y: f32,
+    },
+    MouseButtonUp {
+        window_id: u32,
+        button: MouseButton,
+        clicks: u8,
+        x: f32,
+        y: f32,
+    },
+    MouseWheel {
+        window_id: u32,
+        x: f32,
+        y: f32,
+    },
+    TextInput {
+        window_id: u32,
+        text: String,
+    },
+    /// In-progress IME composition. `start` and `length` select the part of
+    /// `text` being edited, in characters.
+    TextEditing {
+        window_id: u32,
+        text: String,
+        start: i32,
+        length: i32,
+    },
+    /// A drag and drop operation entered the window. `x` and `y` are in
+    /// window coordinates for all drop events.
+    DropBegin {
+        window_id: u32,
+        x: f32,
+        y: f32,
+    },
+    /// The dragged item moved over the window.
+    DropPosition {
+        window_id: u32,
+        x: f32,
+        y: f32,
+    },
+    DropFile {
+        window_id: u32,
+        path: String,
+        x: f32,
+        y: f32,
+    },
+    DropText {
+        window_id: u32,
+        text: String,
+        x: f32,
+        y: f32,
+    },
+    /// Every file and text of the operation has been delivered.
+    DropComplete {
+        window_id: u32,
+        x: f32,
+        y: f32,
+    },
+    GamepadAdded {
+        which: u32,
+    },
+    GamepadRemoved {
+        which: u32,
+    },
+    GamepadButtonDown {
+        which: u32,
+        button: GamepadButton,
+    },
+    GamepadButtonUp {
+        which: u32,
+        button: GamepadButton,
+    },
+    GamepadAxisMotion {
+        which: u32,
+        axis: GamepadAxis,
+        value: i16,
+    },
+    /// An SDL event this wrapper does not translate yet.
+    Other {
+        event_type: u32,
+    },
+}
+
+#[allow(dead_code)]
+impl Event {
+    /// The window the event is targeted at, if any.
+    pub fn window_id(&self) -> Option<u32> {
+        match self {
+            Event::WindowResized { window_id, .. }
+            | Event::WindowPixelSizeChanged { window_id, .. }
+            | Event::WindowDisplayScaleChanged { window_id }
+            | Event::WindowMoved { window_id, .. }
+            | Event::WindowFocusGained { window_id }
+            | Event::WindowFocusLost { window_id }
+            | Event::WindowCloseRequested { window_id }
+            | Event::KeyDown { window_id, .. }
+            | Event::KeyUp { window_id, .. }
+            | Event::MouseMotion { window_id, .. }
+            | Event::MouseButtonDown { window_id, .. }
+            | Event::MouseButtonUp { window_id, .. }
+            | Event::MouseWheel { window_id, .. }
+            | Event::TextInput { window_id, .. }
+            | Event::TextEditing { window_id, .. }
+            | Event::DropBegin { window_id, .. }
+            | Event::DropPosition { window_id, .. }
+            | Event::DropFile { window_id, .. }
+            | Event::DropText { window_id, .. }
+            | Event::DropComplete { window_id, .. } => Some(*window_id),
+            Event::Quit
+            | Event::GamepadAdded { .. }
+            | Event::GamepadRemoved { .. }
+            | Event::GamepadButtonDown { .. }
+            | Event::GamepadButtonUp { .. }
+            | Event::GamepadAxisMotion { .. }
+            | Event::Other { .. } => None,
+        }
+    }
+
+    pub fn from_sdl(raw: &SDL_Event) -> Event {
+        unsafe {
+            match events::SDL_EventType(raw.r#type) {
+                events::SDL_EVENT_QUIT => Event::Quit,
+                events::SDL_EVENT_WINDOW_RESIZED => Event::WindowResized {
+                    window_id: raw.window.windowID,
+                    width: raw.window.data1,
+                    height: raw.window.data2,
+                },
+                events::SDL_EVENT_WINDOW_PIXEL_SIZE_CHANGED => Event::WindowPixelSizeChanged {
+                    window_id: raw.window.windowID,
+                    width: raw.window.data1,
+                    height: raw.window.data2,
+                },
+                events::SDL_EVENT_WINDOW_DISPLAY_SCALE_CHANGED => {
+                    Event::WindowDisplayScaleChanged {
+                        window_id: raw.window.windowID,
+                    }
+                }
+                events::SDL_EVENT_WINDOW_MOVED => Event::WindowMoved {
+                    window_id: raw.window.windowID,
+                    x: raw.window.data1,
+                    y: raw.window.data2,
+                },
+                events::SDL_EVENT_WINDOW_FOCUS_GAINED => Event::WindowFocusGained {
+                    window_id: raw.window.windowID,
+                },
+                events::SDL_EVENT_WINDOW_FOCUS_LOST => Event::WindowFocusLost {
+                    window_id: raw.window.windowID,
+                },
+                events::SDL_EVENT_WINDOW_CLOSE_REQUESTED => Event::WindowCloseRequested {
+                    window_id: raw.window.windowID,
+                },
+                events::SDL_EVENT_KEY_DOWN => Event::KeyDown {
+                    window_id: raw.key.windowID,
+                    scancode: Scancode(raw.key.scancode.0 as u32),
+                    keycode: Keycode(raw.key.key),
+                    modifiers: KeyModifiers(raw.key.r#mod),
+                    repeat: raw.key.repeat,
+                },
+                events::SDL_EVENT_KEY_UP => Event::KeyUp {
+                    window_id: raw.key.windowID,
+                    scancode: Scancode(raw.key.scancode.0 as u32),
+                    keycode: Keycode(raw.key.key),
+                    modifiers: KeyModifiers(raw.key.r#mod),
+                },
+                events::SDL_EVENT_MOUSE_MOTION => Event::MouseMotion {
+                    window_id: raw.motion.windowID,
+                    x: raw.motion.x,
+                    y: raw.motion.y,
+                    xrel: raw.motion.xrel,
+                    yrel: raw.motion.yrel,
+                },
+                events::SDL_EVENT_MOUSE_BUTTON_DOWN => Event::MouseButtonDown {
+                    window_id: raw.button.windowID,
+                    button: sdl_to_mouse_button(raw.button.button),
+                    clicks: raw.button.clicks,
+                    x: raw.button.x,
+                    y: raw.button.y,
+                },
+                events::SDL_EVENT_MOUSE_BUTTON_UP => Event::MouseButtonUp {
+                    window_id: raw.button.windowID,
+                    button: sdl_to_mouse_button(raw.button.button),
+                    clicks: raw.button.clicks,
+                    x: raw.button.x,
+                    y: raw.button.y,
+                },
+                events::SDL_EVENT_MOUSE_WHEEL => {
+                    let mut x = raw.wheel.x;
+                    let mut y = raw.wheel.y;
+                    if raw.wheel.direction == mouse::SDL_MOUSEWHEEL_FLIPPED {
+                        x = -x;
+                        y = -y;
+                    }
+
+                    Event::MouseWheel {
+                        window_id: raw.wheel.windowID,
+                        x,
+                        y,
+                    }
+                }
+                events::SDL_EVENT_TEXT_INPUT => Event::TextInput {
+                    window_id: raw.text.windowID,
+                    text: cstr_to_string(raw.text.text),
+                },
+                events::SDL_EVENT_TEXT_EDITING => Event::TextEditing {
+                    window_id: raw.edit.windowID,
+                    text: cstr_to_string(raw.edit.text),
+                    start: raw.edit.start,
+                    length: raw.edit.length,
+                },
+                events::SDL_EVENT_DROP_BEGIN => Event::DropBegin {
+                    window_id: raw.drop.windowID,
+                    x: raw.drop.x,
+                    y: raw.drop.y,
+                },
+                events::SDL_EVENT_DROP_POSITION => Event::DropPosition {
+                    window_id: raw.drop.windowID,
+                    x: raw.drop.x,
+                    y: raw.drop.y,
+                },
+                events::SDL_EVENT_DROP_FILE => Event::DropFile {
+                    window_id: raw.drop.windowID,
+                    path: cstr_to_string(raw.drop.data),
+                    x: raw.drop.x,
+                    y: raw.drop.y,
+                },
+                events::SDL_EVENT_DROP_TEXT => Event::DropText {
+                    window_id: raw.drop.windowID,
+                    text: cstr_to_string(raw.drop.data),
+                    x: raw.drop.x,
+                    y: raw.drop.y,
+                },
+                events::SDL_EVENT_DROP_COMPLETE => Event::DropComplete {
+                    window_id: raw.drop.windowID,
+                    x: raw.drop.x,
+                    y: raw.drop.y,
+                },
+                events::SDL_EVENT_GAMEPAD_ADDED => Event::GamepadAdded {
+                    which: raw.gdevice.which,
+                },
+                events::SDL_EVENT_GAMEPAD_REMOVED => Event::GamepadRemoved {
+                    which: raw.gdevice.which,
+                },
+                events::SDL_EVENT_GAMEPAD_BUTTON_DOWN | events::SDL_EVENT_GAMEPAD_BUTTON_UP => {
+                    let which = raw.gbutton.which;
+                    match sdl_to_gamepad_button(raw.gbutton.button) {
+                        Some(button) if raw.gbutton.down => {
+                            Event::GamepadButtonDown { which, button }
+                        }
+                        Some(button) => Event::GamepadButtonUp { which, button },
+                        None => Event::Other {
+                            event_type: raw.r#type,
+                        },
+                    }
+                }
+                events::SDL_EVENT_GAMEPAD_AXIS_MOTION => {
+                    match sdl_to_gamepad_axis(raw.gaxis.axis) {
+                        Some(axis) => Event::GamepadAxisMotion {
+                            which: raw.gaxis.which,
+                            axis,
+                            value: raw.gaxis.value,
+                        },
+                        None => Event::Other {
+                            event_type: raw.r#type,
+                        },
+                    }
+                }
+                _ => Event::Other {
+                    event_type: raw.r#type,
+                },
+            }
+        }
+    }
+}
+
+pub fn cstr_to_string(ptr: *const std::ffi::c_char) -> String {
+    if ptr.is_null() {
+        return String::new();
+    }
+
+    let cstr = unsafe { CStr::from_ptr(ptr) };
+    cstr.to_string_lossy().into_owned()
+}
+
+/// Drains the SDL event queue. Obtained from `Sdl::event_pump`.
+#[derive(Debug)]
+pub struct EventPump {
+    _sdl: Arc<Sdl>,
+}
+
+#[allow(dead_code)]
+impl EventPump {
+    pub fn new(sdl: &Arc<Sdl>) -> Self {
+        EventPump {
+            _sdl: Arc::clone(sdl),
+        }
+    }
+
+    /// Pop the next pending event, or `None` if the queue is empty.
+    pub fn poll_event(&mut self) -> Option<Event> {
+        let mut raw: SDL_Event = unsafe { std::mem::zeroed() };
+        let res = unsafe { events::SDL_PollEvent(&mut raw) };
+        if !res {
+            return None;
+        }
+
+        Some(Event::from_sdl(&raw))
+    }
+
+    /// Block until an event arrives or `timeout_ms` elapses.
+    pub fn wait_event_timeout(&mut self, timeout_ms: i32) -> Option<Event> {
+        let mut raw: SDL_Event = unsafe { std::mem::zeroed() };
+        let res = unsafe { events::SDL_WaitEventTimeout(&mut raw, timeout_ms) };
+        if !res {
+            return None;
+        }
+
+        Some(Event::from_sdl(&raw))
+    }
+
+    /// Iterate over every event pending at this point, emptying the queue.
+    pub fn poll_iter(&mut self) -> EventPollIter<'_> {
+        EventPollIter { pump: self }
+    }
+
+    /// Collect every pending event, emptying the queue.
+    pub fn drain(&mut self) -> Vec<Event> {
+        self.poll_iter().collect()
+    }
+}
+
+pub struct EventPollIter<'a> {
+    pump: &'a mut EventPump,
+}
+
+impl Iterator for EventPollIter<'_> {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Event> {
+        self.pump.poll_event()
+    }
+}
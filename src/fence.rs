@@ -13,7 +13,11 @@ pub struct EstFence {
 #[allow(dead_code)]
 impl EstFence {
     pub fn wait(&self) {
-        unsafe { gpu::SDL_WaitForGPUFences(self.device.device, false, &self.fence, 0) };
+        unsafe { gpu::SDL_WaitForGPUFences(self.device.device, true, &self.fence, 1) };
+    }
+
+    pub fn is_ready(&self) -> bool {
+        unsafe { gpu::SDL_QueryGPUFence(self.device.device, self.fence) }
     }
 }
 
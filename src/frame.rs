@@ -0,0 +1,246 @@
+use std::sync::Arc;
+
+use crate::{
+    buffer::{GPUTransferBuffer, GPUTransferUsage},
+    command_buffer::CommandBuffer,
+    device::GPUDevice,
+    error::Error,
+    fence::EstFence,
+};
+
+/// A slice of the current frame's scratch upload buffer.
+/// Valid until the same frame slot comes around again.
+#[derive(Debug, Clone)]
+pub struct GPUScratchAllocation {
+    pub buffer: Arc<GPUTransferBuffer>,
+    pub offset: u32,
+    pub size: u32,
+}
+
+#[allow(dead_code)]
+impl GPUScratchAllocation {
+    pub fn write(&self, data: &[u8]) -> Result<(), Error> {
+        if data.len() > self.size as usize {
+            return Err(Error::SizeMismatch(
+                "Data size is larger than scratch allocation size".to_string(),
+            ));
+        }
+
+        self.buffer.write_mapped(self.offset, data, false)
+    }
+
+    pub fn write_t<T>(&self, data: &[T]) -> Result<(), Error> {
+        let data_size = std::mem::size_of_val(data);
+        let new_data = unsafe {
+            let data_ptr = data.as_ptr() as *const u8;
+            std::slice::from_raw_parts(data_ptr, data_size)
+        };
+
+        self.write(new_data)
+    }
+}
+
+#[derive(Debug)]
+struct FrameResources {
+    fence: Option<Arc<EstFence>>,
+    scratch: Arc<GPUTransferBuffer>,
+    scratch_offset: u32,
+    transfer_buffers: Vec<Arc<GPUTransferBuffer>>,
+    transfer_buffers_used: usize,
+}
+
+/// Tracks N frames in flight. Each frame slot owns its own upload resources,
+/// which are only reused once the fence of the frame that last used them has
+/// signaled, so data streamed for frame N+1 never overwrites what frame N reads.
+#[derive(Debug)]
+pub struct FrameContext {
+    device: Arc<GPUDevice>,
+    frames: Vec<FrameResources>,
+    frame_index: usize,
+    frame_count: u64,
+    in_frame: bool,
+}
+
+#[allow(dead_code)]
+impl FrameContext {
+    pub fn new(
+        device: &Arc<GPUDevice>,
+        frames_in_flight: usize,
+        scratch_size: u32,
+    ) -> Result<Self, Error> {
+        if frames_in_flight == 0 {
+            return Err(Error::InvalidUsage(
+                "Frames in flight must be at least 1".to_string(),
+            ));
+        }
+
+        let mut frames = Vec::with_capacity(frames_in_flight);
+        for _ in 0..frames_in_flight {
+            let scratch =
+                device.create_transfer_buffer(scratch_size as usize, GPUTransferUsage::Upload)?;
+
+            frames.push(FrameResources {
+                fence: None,
+                scratch,
+                scratch_offset: 0,
+                transfer_buffers: Vec::new(),
+                transfer_buffers_used: 0,
+            });
+        }
+
+        Ok(FrameContext {
+            device: Arc::clone(device),
+            frames,
+            frame_index: frames_in_flight - 1,
+            frame_count: 0,
+            in_frame: false,
+        })
+    }
+
+    pub fn frames_in_flight(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Index of the current frame slot, in `0..frames_in_flight`.
+    pub fn frame_index(&self) -> usize {
+        self.frame_index
+    }
+
+    /// Number of frames begun since creation.
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// Advance to the next frame slot, waiting for the GPU to finish the frame
+    /// that last used it, and acquire a command buffer for the new frame.
+    pub fn begin_frame(&mut self) -> Result<CommandBuffer, Error> {
+        if self.in_frame {
+            return Err(Error::InvalidUsage("Frame is already begun!".to_string()));
+        }
+
+        self.frame_index = (self.frame_index + 1) % self.frames.len();
+
+        let frame = &mut self.frames[self.frame_index];
+        if let Some(fence) = frame.fence.take() {
+            fence.wait();
+        }
+
+        frame.scratch_offset = 0;
+        frame.transfer_buffers_used = 0;
+
+        let command_buffer = self.device.acquire_command_buffer()?;
+
+        self.in_frame = true;
+        self.frame_count += 1;
+
+        Ok(command_buffer)
+    }
+
+    /// Submit the frame's command buffer and remember its fence for the slot.
+    pub fn end_frame(&mut self, mut command_buffer: CommandBuffer) -> Result<(), Error> {
+        if !self.in_frame {
+            return Err(Error::InvalidUsage("Frame is not begun!".to_string()));
+        }
+
+        self.in_frame = false;
+
+        let fence = command_buffer.submit_and_acquire_fence()?;
+        self.frames[self.frame_index].fence = Some(fence);
+
+        Ok(())
+    }
+
+    /// Abandon the frame without submitting any of its recorded work.
+    pub fn cancel_frame(&mut self, mut command_buffer: CommandBuffer) -> Result<(), Error> {
+        if !self.in_frame {
+            return Err(Error::InvalidUsage("Frame is not begun!".to_string()));
+        }
+
+        self.in_frame = false;
+
+        command_buffer.cancel()
+    }
+
+    /// Hand out an upload transfer buffer owned by the current frame slot.
+    /// Buffers are pooled per slot and reused once that slot comes around again.
+    pub fn transfer_buffer(&mut self, size: u32) -> Result<Arc<GPUTransferBuffer>, Error> {
+        if !self.in_frame {
+            return Err(Error::InvalidUsage("Frame is not begun!".to_string()));
+        }
+
+        let frame = &mut self.frames[self.frame_index];
+
+        let used = frame.transfer_buffers_used;
+        let free = frame.transfer_buffers[used..]
+            .iter()
+            .position(|buffer| buffer.size >= size);
+
+        if let Some(index) = free {
+            frame.transfer_buffers.swap(used, used + index);
+        } else {
+            let buffer = self
+                .device
+                .create_transfer_buffer(size as usize, GPUTransferUsage::Upload)?;
+
+            frame.transfer_buffers.push(buffer);
+            let last = frame.transfer_buffers.len() - 1;
+            frame.transfer_buffers.swap(used, last);
+        }
+
+        frame.transfer_buffers_used += 1;
+
+        Ok(Arc::clone(&frame.transfer_buffers[used]))
+    }
+
+    /// Bump-allocate `size` bytes from the current frame's scratch upload buffer.
+    /// `alignment` must be a power of two.
+    pub fn allocate(&mut self, size: u32, alignment: u32) -> Result<GPUScratchAllocation, Error> {
+        if !self.in_frame {
+            return Err(Error::InvalidUsage("Frame is not begun!".to_string()));
+        }
+
+        if !alignment.is_power_of_two() {
+            return Err(Error::InvalidUsage(
+                "Alignment must be a power of two".to_string(),
+            ));
+        }
+
+        let frame = &mut self.frames[self.frame_index];
+
+        let out_of_space =
+            || Error::SizeMismatch("Frame scratch buffer is out of space".to_string());
+
+        let offset = match frame.scratch_offset.checked_add(alignment - 1) {
+            Some(offset) => offset & !(alignment - 1),
+            None => return Err(out_of_space()),
+        };
+
+        let end = match offset.checked_add(size) {
+            Some(end) if end <= frame.scratch.size => end,
+            _ => return Err(out_of_space()),
+        };
+
+        frame.scratch_offset = end;
+
+        Ok(GPUScratchAllocation {
+            buffer: Arc::clone(&frame.scratch),
+            offset,
+            size,
+        })
+    }
+
+    /// Block until every frame in flight has finished on the GPU.
+    pub fn wait_idle(&mut self) {
+        for frame in self.frames.iter_mut() {
+            if let Some(fence) = frame.fence.take() {
+                fence.wait();
+            }
+        }
+    }
+}
+
+impl Drop for FrameContext {
+    fn drop(&mut self) {
+        self.wait_idle();
+    }
+}
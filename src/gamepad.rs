@@ -0,0 +1,437 @@
+use std::{collections::HashMap, sync::Arc};
+
+use sdl3_sys::gamepad;
+
+use crate::{error::Error, event::Event, math::Vector2, sdl::Sdl};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadButton {
+    South,
+    East,
+    West,
+    North,
+    Back,
+    Guide,
+    Start,
+    LeftStick,
+    RightStick,
+    LeftShoulder,
+    RightShoulder,
+    DpadUp,
+    DpadDown,
+    DpadLeft,
+    DpadRight,
+    Misc1,
+    Touchpad,
+}
+
+const GAMEPAD_BUTTONS: [GamepadButton; 17] = [
+    GamepadButton::South,
+    GamepadButton::East,
+    GamepadButton::West,
+    GamepadButton::North,
+    GamepadButton::Back,
+    GamepadButton::Guide,
+    GamepadButton::Start,
+    GamepadButton::LeftStick,
+    GamepadButton::RightStick,
+    GamepadButton::LeftShoulder,
+    GamepadButton::RightShoulder,
+    GamepadButton::DpadUp,
+    GamepadButton::DpadDown,
+    GamepadButton::DpadLeft,
+    GamepadButton::DpadRight,
+    GamepadButton::Misc1,
+    GamepadButton::Touchpad,
+];
+
+pub fn gamepad_button_to_sdl(button: GamepadButton) -> gamepad::SDL_GamepadButton {
+    match button {
+        GamepadButton::South => gamepad::SDL_GAMEPAD_BUTTON_SOUTH,
+        GamepadButton::East => gamepad::SDL_GAMEPAD_BUTTON_EAST,
+        GamepadButton::West => gamepad::SDL_GAMEPAD_BUTTON_WEST,
+        GamepadButton::North => gamepad::SDL_GAMEPAD_BUTTON_NORTH,
+        GamepadButton::Back => gamepad::SDL_GAMEPAD_BUTTON_BACK,
+        GamepadButton::Guide => gamepad::SDL_GAMEPAD_BUTTON_GUIDE,
+        GamepadButton::Start => gamepad::SDL_GAMEPAD_BUTTON_START,
+        GamepadButton::LeftStick => gamepad::SDL_GAMEPAD_BUTTON_LEFT_STICK,
+        GamepadButton::RightStick => gamepad::SDL_GAMEPAD_BUTTON_RIGHT_STICK,
+        GamepadButton::LeftShoulder => gamepad::SDL_GAMEPAD_BUTTON_LEFT_SHOULDER,
+        GamepadButton::RightShoulder => gamepad::SDL_GAMEPAD_BUTTON_RIGHT_SHOULDER,
+        GamepadButton::DpadUp => gamepad::SDL_GAMEPAD_BUTTON_DPAD_UP,
+        GamepadButton::DpadDown => gamepad::SDL_GAMEPAD_BUTTON_DPAD_DOWN,
+        GamepadButton::DpadLeft => gamepad::SDL_GAMEPAD_BUTTON_DPAD_LEFT,
+        GamepadButton::DpadRight => gamepad::SDL_GAMEPAD_BUTTON_DPAD_RIGHT,
+        GamepadButton::Misc1 => gamepad::SDL_GAMEPAD_BUTTON_MISC1,
+        GamepadButton::Touchpad => gamepad::SDL_GAMEPAD_BUTTON_TOUCHPAD,
+    }
+}
+
+pub fn sdl_to_gamepad_button(button: u8) -> Option<GamepadButton> {
+    GAMEPAD_BUTTONS
+        .iter()
+        .copied()
+        .find(|b| gamepad_button_to_sdl(*b).0 == button as _)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadAxis {
+    LeftX,
+    LeftY,
+    RightX,
+    RightY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+const GAMEPAD_AXES: [GamepadAxis; 6] = [
+    GamepadAxis::LeftX,
+    GamepadAxis::LeftY,
+    GamepadAxis::RightX,
+    GamepadAxis::RightY,
+    GamepadAxis::LeftTrigger,
+    GamepadAxis::RightTrigger,
+];
+
+pub fn gamepad_axis_to_sdl(axis: GamepadAxis) -> gamepad::SDL_GamepadAxis {
+    match axis {
+        GamepadAxis::LeftX => gamepad::SDL_GAMEPAD_AXIS_LEFTX,
+        GamepadAxis::LeftY => gamepad::SDL_GAMEPAD_AXIS_LEFTY,
+        GamepadAxis::RightX => gamepad::SDL_GAMEPAD_AXIS_RIGHTX,
+        GamepadAxis::RightY => gamepad::SDL_GAMEPAD_AXIS_RIGHTY,
+        GamepadAxis::LeftTrigger => gamepad::SDL_GAMEPAD_AXIS_LEFT_TRIGGER,
+        GamepadAxis::RightTrigger => gamepad::SDL_GAMEPAD_AXIS_RIGHT_TRIGGER,
+    }
+}
+
+pub fn sdl_to_gamepad_axis(axis: u8) -> Option<GamepadAxis> {
+    GAMEPAD_AXES
+        .iter()
+        .copied()
+        .find(|a| gamepad_axis_to_sdl(*a).0 == axis as _)
+}
+
+/// Radial deadzone for a stick, as fractions of full deflection.
+/// Input below `inner` reads as zero, input above `outer` as full deflection,
+/// and the range in between is rescaled so there is no jump at the edge.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Deadzone {
+    pub inner: f32,
+    pub outer: f32,
+}
+
+impl Default for Deadzone {
+    fn default() -> Self {
+        Deadzone {
+            inner: 0.15,
+            outer: 0.95,
+        }
+    }
+}
+
+impl Deadzone {
+    pub fn apply(&self, x: f32, y: f32) -> Vector2 {
+        let magnitude = (x * x + y * y).sqrt();
+        if magnitude == 0.0 {
+            return Vector2::new(0.0, 0.0);
+        }
+
+        let scaled = self.rescale(magnitude);
+
+        Vector2::new(x / magnitude * scaled, y / magnitude * scaled)
+    }
+
+    pub fn apply_trigger(&self, value: f32) -> f32 {
+        self.rescale(value)
+    }
+
+    fn rescale(&self, value: f32) -> f32 {
+        if value <= self.inner {
+            return 0.0;
+        }
+
+        // Also catches `outer <= inner`, which would divide by zero or invert
+        // the range below.
+        if value >= self.outer {
+            return 1.0;
+        }
+
+        (value - self.inner) / (self.outer - self.inner)
+    }
+}
+
+/// Per-frame polled state of one gamepad, with deadzones applied.
+#[derive(Debug, Clone, Copy)]
+pub struct GamepadState {
+    buttons: u32,
+    previous_buttons: u32,
+    pub left_stick: Vector2,
+    pub right_stick: Vector2,
+    pub left_trigger: f32,
+    pub right_trigger: f32,
+}
+
+impl Default for GamepadState {
+    fn default() -> Self {
+        GamepadState {
+            buttons: 0,
+            previous_buttons: 0,
+            left_stick: Vector2::new(0.0, 0.0),
+            right_stick: Vector2::new(0.0, 0.0),
+            left_trigger: 0.0,
+            right_trigger: 0.0,
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl GamepadState {
+    pub fn is_button_down(&self, button: GamepadButton) -> bool {
+        self.buttons & button_bit(button) != 0
+    }
+
+    pub fn was_button_pressed(&self, button: GamepadButton) -> bool {
+        let bit = button_bit(button);
+        self.buttons & bit != 0 && self.previous_buttons & bit == 0
+    }
+
+    pub fn was_button_released(&self, button: GamepadButton) -> bool {
+        let bit = button_bit(button);
+        self.buttons & bit == 0 && self.previous_buttons & bit != 0
+    }
+}
+
+fn button_bit(button: GamepadButton) -> u32 {
+    1 << (button as u32)
+}
+
+fn normalize_axis(value: i16) -> f32 {
+    (value as f32 / i16::MAX as f32).clamp(-1.0, 1.0)
+}
+
+#[derive(Debug)]
+pub struct Gamepad {
+    gamepad: *mut gamepad::SDL_Gamepad,
+    id: u32,
+    state: GamepadState,
+    pub left_deadzone: Deadzone,
+    pub right_deadzone: Deadzone,
+    pub trigger_deadzone: Deadzone,
+}
+
+#[allow(dead_code)]
+impl Gamepad {
+    fn open(id: u32) -> Result<Self, Error> {
+        let ptr = unsafe { gamepad::SDL_OpenGamepad(id) };
+        if ptr.is_null() {
+            return Err(Error::sdl("Failed to open gamepad"));
+        }
+
+        Ok(Gamepad {
+            gamepad: ptr,
+            id,
+            state: GamepadState::default(),
+            left_deadzone: Deadzone::default(),
+            right_deadzone: Deadzone::default(),
+            trigger_deadzone: Deadzone {
+                inner: 0.05,
+                outer: 1.0,
+            },
+        })
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn name(&self) -> String {
+        let name = unsafe { gamepad::SDL_GetGamepadName(self.gamepad) };
+        if name.is_null() {
+            return String::new();
+        }
+
+        let cstr = unsafe { std::ffi::CStr::from_ptr(name) };
+        cstr.to_string_lossy().into_owned()
+    }
+
+    pub fn state(&self) -> &GamepadState {
+        &self.state
+    }
+
+    /// Rumble the low and high frequency motors (0.0 to 1.0) for `duration_ms`.
+    /// A new call replaces any rumble in progress; zero intensities stop it.
+    pub fn rumble(&self, low: f32, high: f32, duration_ms: u32) -> Result<(), Error> {
+        let low = (low.clamp(0.0, 1.0) * u16::MAX as f32) as u16;
+        let high = (high.clamp(0.0, 1.0) * u16::MAX as f32) as u16;
+
+        let res = unsafe { gamepad::SDL_RumbleGamepad(self.gamepad, low, high, duration_ms) };
+        if !res {
+            return Err(Error::sdl("Failed to rumble gamepad"));
+        }
+
+        Ok(())
+    }
+
+    pub fn raw_axis(&self, axis: GamepadAxis) -> i16 {
+        unsafe { gamepad::SDL_GetGamepadAxis(self.gamepad, gamepad_axis_to_sdl(axis)) }
+    }
+
+    fn poll(&mut self) {
+        let mut buttons = 0;
+        for button in GAMEPAD_BUTTONS {
+            let down = unsafe {
+                gamepad::SDL_GetGamepadButton(self.gamepad, gamepad_button_to_sdl(button))
+            };
+
+            if down {
+                buttons |= button_bit(button);
+            }
+        }
+
+        self.state.previous_buttons = self.state.buttons;
+        self.state.buttons = buttons;
+
+        self.state.left_stick = self.left_deadzone.apply(
+            normalize_axis(self.raw_axis(GamepadAxis::LeftX)),
+            normalize_axis(self.raw_axis(GamepadAxis::LeftY)),
+        );
+        self.state.right_stick = self.right_deadzone.apply(
+            normalize_axis(self.raw_axis(GamepadAxis::RightX)),
+            normalize_axis(self.raw_axis(GamepadAxis::RightY)),
+        );
+        self.state.left_trigger = self
+            .trigger_deadzone
+            .apply_trigger(normalize_axis(self.raw_axis(GamepadAxis::LeftTrigger)));
+        self.state.right_trigger = self
+            .trigger_deadzone
+            .apply_trigger(normalize_axis(self.raw_axis(GamepadAxis::RightTrigger)));
+    }
+}
+
+impl Drop for Gamepad {
+    fn drop(&mut self) {
+        unsafe {
+            gamepad::SDL_CloseGamepad(self.gamepad);
+        }
+    }
+}
+
+/// Opens and closes gamepads as they are plugged in and out, and polls their
+/// state once per frame. Requires the gamepad subsystem (`SdlCreateInfo::gamepad`).
+#[derive(Debug)]
+pub struct GamepadManager {
+    _sdl: Arc<Sdl>,
+    gamepads: HashMap<u32, Gamepad>,
+}
+
+#[allow(dead_code)]
+impl GamepadManager {
+    pub fn new(sdl: &Arc<Sdl>) -> Result<Self, Error> {
+        if !sdl.has_gamepad() {
+            return Err(Error::InvalidUsage(
+                "Gamepad subsystem is not initialized".to_string(),
+            ));
+        }
+
+        Ok(GamepadManager {
+            _sdl: Arc::clone(sdl),
+            gamepads: HashMap::new(),
+        })
+    }
+
+    /// Track hot-plug events. Gamepads already connected at startup are
+    /// reported by SDL as added events too. Returns an error if an added
+    /// gamepad could not be opened.
+    pub fn handle_event(&mut self, event: &Event) -> Result<(), Error> {
+        match event {
+            Event::GamepadAdded { which } => {
+                if self.gamepads.contains_key(which) {
+                    return Ok(());
+                }
+
+                let gamepad = Gamepad::open(*which)?;
+                self.gamepads.insert(*which, gamepad);
+            }
+            Event::GamepadRemoved { which } => {
+                self.gamepads.remove(which);
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Poll every connected gamepad. Call once per frame after handling events.
+    pub fn update(&mut self) {
+        for gamepad in self.gamepads.values_mut() {
+            gamepad.poll();
+        }
+    }
+
+    pub fn get(&self, id: u32) -> Option<&Gamepad> {
+        self.gamepads.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: u32) -> Option<&mut Gamepad> {
+        self.gamepads.get_mut(&id)
+    }
+
+    pub fn ids(&self) -> Vec<u32> {
+        let mut ids: Vec<u32> = self.gamepads.keys().copied().collect();
+        ids.sort();
+
+        ids
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Gamepad> {
+        self.gamepads.values()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_vec2(v: Vector2, x: f32, y: f32) {
+        let (vx, vy) = (v.x, v.y);
+        assert!(
+            (vx - x).abs() < 1e-5 && (vy - y).abs() < 1e-5,
+            "{:?}",
+            (vx, vy)
+        );
+    }
+
+    #[test]
+    fn deadzone_rescales_between_inner_and_outer() {
+        let deadzone = Deadzone {
+            inner: 0.2,
+            outer: 0.8,
+        };
+
+        assert_vec2(deadzone.apply(0.1, 0.0), 0.0, 0.0);
+        assert_vec2(deadzone.apply(0.0, 0.5), 0.0, 0.5);
+        assert_vec2(deadzone.apply(-0.9, 0.0), -1.0, 0.0);
+        assert_eq!(deadzone.apply_trigger(0.2), 0.0);
+        assert!((deadzone.apply_trigger(0.5) - 0.5).abs() < 1e-5);
+        assert_eq!(deadzone.apply_trigger(1.0), 1.0);
+    }
+
+    #[test]
+    fn deadzone_with_empty_or_inverted_range() {
+        for deadzone in [
+            Deadzone {
+                inner: 0.5,
+                outer: 0.5,
+            },
+            Deadzone {
+                inner: 0.6,
+                outer: 0.4,
+            },
+        ] {
+            assert_vec2(deadzone.apply(0.3, 0.0), 0.0, 0.0);
+            assert_vec2(deadzone.apply(0.0, 0.7), 0.0, 1.0);
+            assert_eq!(deadzone.apply_trigger(0.3), 0.0);
+            assert_eq!(deadzone.apply_trigger(0.7), 1.0);
+        }
+    }
+}
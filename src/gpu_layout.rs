@@ -0,0 +1,310 @@
+#![allow(dead_code)]
+
+//! Aligned math types for uniform and storage buffers.
+//!
+//! The types in `math.rs` are packed, which suits vertex data but not shader
+//! buffers: std140 and std430 align `vec2` to 8 bytes and `vec3`, `vec4` and
+//! matrix columns to 16. The types here carry that alignment, so a `#[repr(C)]`
+//! struct built from them has the same offsets as the GLSL block. A `vec3`
+//! followed by a scalar can share 16 bytes in GLSL; here `GPUVec3` always takes
+//! the full 16, so use three `f32` fields plus the scalar for that case.
+//!
+//! Plain arrays are limited to `GPUArrayElement` types, because std140 rounds
+//! the stride of every array element up to 16 bytes; use `GPUVec4` for a
+//! `float[N]` in a uniform block and read `.x` in the shader. Storage buffers
+//! use std430, where `float[N]` and `vec2[N]` are tightly packed; wrap those in
+//! `GPUStd430Array`, which must only be used in storage buffers.
+//!
+//! ```ignore
+//! #[repr(C)]
+//! #[derive(Clone, Copy)]
+//! struct Camera {
+//!     view_proj: GPUMat4,
+//!     position: GPUVec3,
+//!     time: f32,
+//!     _pad: Pad<{ padding_for(84, 16) }>,
+//! }
+//!
+//! unsafe impl GPULayout for Camera {}
+//! const _: () = assert_layout::<Camera>(96, 16);
+//! ```
+
+use std::mem::{align_of, size_of};
+
+use crate::math::{Color, Mat3, Mat4, Vector2, Vector3, Vector4};
+
+/// Types that can be copied into a GPU buffer as raw bytes.
+///
+/// # Safety
+///
+/// Implementors must be `#[repr(C)]` (or a primitive) and contain no implicit
+/// padding bytes; fill gaps with `Pad` so every byte is initialized.
+pub unsafe trait GPULayout: Copy + 'static {
+    fn as_bytes(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self as *const Self as *const u8, size_of::<Self>()) }
+    }
+}
+
+/// Types whose size is a multiple of 16 bytes, so a Rust array of them has the
+/// same stride as a std140 array.
+///
+/// # Safety
+///
+/// Implementors must have an alignment of 16 and a size that is a multiple of 16.
+pub unsafe trait GPUArrayElement: GPULayout {}
+
+/// Types that can be elements of a std430 array, whose stride is the size of
+/// the element.
+///
+/// # Safety
+///
+/// Implementors must have a size that is a multiple of their alignment.
+pub unsafe trait GPUStd430Element: GPULayout {}
+
+/// Array with std430 stride, for storage buffers only. std140 would round the
+/// stride up to 16 bytes, so do not use it in uniform blocks.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GPUStd430Array<T: GPUStd430Element, const N: usize>(pub [T; N]);
+
+/// Fails compilation when `T` does not have the expected size and alignment.
+/// Use as `const _: () = assert_layout::<T>(size, align);`.
+pub const fn assert_layout<T: GPULayout>(size: usize, align: usize) {
+    assert!(size_of::<T>() == size, "GPU type has the wrong size");
+    assert!(align_of::<T>() == align, "GPU type has the wrong alignment");
+}
+
+/// Number of padding bytes needed to move `offset` up to a multiple of `align`.
+pub const fn padding_for(offset: usize, align: usize) -> usize {
+    (align - offset % align) % align
+}
+
+/// Explicit, zero-initialized padding bytes.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pad<const N: usize>([u8; N]);
+
+impl<const N: usize> Pad<N> {
+    pub const ZERO: Pad<N> = Pad([0; N]);
+}
+
+impl<const N: usize> Default for Pad<N> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+#[repr(C, align(8))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct GPUVec2 {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl GPUVec2 {
+    pub fn new(x: f32, y: f32) -> Self {
+        GPUVec2 { x, y }
+    }
+}
+
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct GPUVec3 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    _pad: f32,
+}
+
+impl GPUVec3 {
+    pub fn new(x: f32, y: f32, z: f32) -> Self {
+        GPUVec3 { x, y, z, _pad: 0.0 }
+    }
+}
+
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct GPUVec4 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+impl GPUVec4 {
+    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        GPUVec4 { x, y, z, w }
+    }
+}
+
+/// `mat3` as laid out by std140 and std430: three columns padded to 16 bytes.
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GPUMat3 {
+    pub cols: [[f32; 4]; 3],
+}
+
+#[repr(C, align(16))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GPUMat4 {
+    pub cols: [[f32; 4]; 4],
+}
+
+unsafe impl GPULayout for f32 {}
+unsafe impl GPULayout for u32 {}
+unsafe impl GPULayout for i32 {}
+unsafe impl<const N: usize> GPULayout for Pad<N> {}
+unsafe impl GPULayout for GPUVec2 {}
+unsafe impl GPULayout for GPUVec3 {}
+unsafe impl GPULayout for GPUVec4 {}
+unsafe impl GPULayout for GPUMat3 {}
+unsafe impl GPULayout for GPUMat4 {}
+unsafe impl<T: GPUArrayElement, const N: usize> GPULayout for [T; N] {}
+
+unsafe impl GPUArrayElement for GPUVec3 {}
+unsafe impl GPUArrayElement for GPUVec4 {}
+unsafe impl GPUArrayElement for GPUMat3 {}
+unsafe impl GPUArrayElement for GPUMat4 {}
+unsafe impl<T: GPUArrayElement, const N: usize> GPUArrayElement for [T; N] {}
+
+unsafe impl<T: GPUStd430Element, const N: usize> GPULayout for GPUStd430Array<T, N> {}
+unsafe impl<T: GPUArrayElement> GPUStd430Element for T {}
+unsafe impl GPUStd430Element for f32 {}
+unsafe impl GPUStd430Element for u32 {}
+unsafe impl GPUStd430Element for i32 {}
+unsafe impl GPUStd430Element for GPUVec2 {}
+
+const _: () = assert_layout::<GPUVec2>(8, 8);
+const _: () = assert_layout::<GPUVec3>(16, 16);
+const _: () = assert_layout::<GPUVec4>(16, 16);
+const _: () = assert_layout::<GPUMat3>(48, 16);
+const _: () = assert_layout::<GPUMat4>(64, 16);
+
+impl From<Vector2> for GPUVec2 {
+    fn from(v: Vector2) -> Self {
+        GPUVec2::new(v.x, v.y)
+    }
+}
+
+impl From<Vector3> for GPUVec3 {
+    fn from(v: Vector3) -> Self {
+        GPUVec3::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Vector4> for GPUVec4 {
+    fn from(v: Vector4) -> Self {
+        GPUVec4::new(v.x, v.y, v.z, v.w)
+    }
+}
+
+impl From<Color> for GPUVec4 {
+    fn from(c: Color) -> Self {
+        GPUVec4::new(c.r, c.g, c.b, c.a)
+    }
+}
+
+impl From<Mat3> for GPUMat3 {
+    fn from(m: Mat3) -> Self {
+        let [a, b, c] = m.cols;
+
+        GPUMat3 {
+            cols: [
+                [a[0], a[1], a[2], 0.0],
+                [b[0], b[1], b[2], 0.0],
+                [c[0], c[1], c[2], 0.0],
+            ],
+        }
+    }
+}
+
+impl From<Mat4> for GPUMat4 {
+    fn from(m: Mat4) -> Self {
+        GPUMat4 { cols: m.cols }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem::{align_of, offset_of, size_of};
+
+    use super::*;
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct Camera {
+        view_proj: GPUMat4,
+        normal: GPUMat3,
+        position: GPUVec3,
+        viewport: GPUVec2,
+        time: f32,
+        _pad: Pad<{ padding_for(140, 16) }>,
+        lights: [GPUVec4; 2],
+    }
+
+    unsafe impl GPULayout for Camera {}
+
+    #[test]
+    fn composite_struct_matches_std140() {
+        // Offsets as computed by std140 for the equivalent GLSL block.
+        assert_eq!(offset_of!(Camera, view_proj), 0);
+        assert_eq!(offset_of!(Camera, normal), 64);
+        assert_eq!(offset_of!(Camera, position), 112);
+        assert_eq!(offset_of!(Camera, viewport), 128);
+        assert_eq!(offset_of!(Camera, time), 136);
+        assert_eq!(offset_of!(Camera, lights), 144);
+        assert_eq!(size_of::<Camera>(), 176);
+        assert_eq!(align_of::<Camera>(), 16);
+    }
+
+    #[test]
+    fn as_bytes_covers_whole_struct() {
+        let camera = Camera {
+            view_proj: GPUMat4::from(Mat4::IDENTITY),
+            normal: GPUMat3::from(Mat3::IDENTITY),
+            position: GPUVec3::new(1.0, 2.0, 3.0),
+            viewport: GPUVec2::new(640.0, 480.0),
+            time: 0.5,
+            _pad: Pad::ZERO,
+            lights: [GPUVec4::default(); 2],
+        };
+
+        let bytes = camera.as_bytes();
+        assert_eq!(bytes.len(), 176);
+        assert_eq!(&bytes[112..116], &1.0f32.to_ne_bytes());
+        assert_eq!(&bytes[136..140], &0.5f32.to_ne_bytes());
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    struct Particles {
+        weights: GPUStd430Array<f32, 3>,
+        _pad: Pad<{ padding_for(12, 8) }>,
+        offsets: GPUStd430Array<GPUVec2, 2>,
+        colors: GPUStd430Array<GPUVec4, 2>,
+    }
+
+    unsafe impl GPULayout for Particles {}
+
+    #[test]
+    fn std430_arrays_are_tightly_packed() {
+        // Offsets as computed by std430 for the equivalent GLSL block.
+        assert_eq!(offset_of!(Particles, weights), 0);
+        assert_eq!(offset_of!(Particles, offsets), 16);
+        assert_eq!(offset_of!(Particles, colors), 32);
+        assert_eq!(size_of::<Particles>(), 64);
+        assert_eq!(size_of::<GPUStd430Array<f32, 3>>(), 12);
+        assert_eq!(size_of::<GPUStd430Array<GPUVec3, 3>>(), 48);
+
+        let particles = Particles {
+            weights: GPUStd430Array([1.0, 2.0, 3.0]),
+            _pad: Pad::ZERO,
+            offsets: GPUStd430Array([GPUVec2::new(4.0, 5.0); 2]),
+            colors: GPUStd430Array([GPUVec4::default(); 2]),
+        };
+
+        let bytes = particles.as_bytes();
+        assert_eq!(&bytes[8..12], &3.0f32.to_ne_bytes());
+        assert_eq!(&bytes[28..32], &5.0f32.to_ne_bytes());
+    }
+}
@@ -0,0 +1,479 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    error::Error,
+    event::{Event, KeyModifiers, MouseButton, Scancode},
+    math::Vector2,
+};
+
+/// Polling-style view of keyboard and mouse built from the event stream.
+/// Call `begin_frame` once per frame before feeding that frame's events
+/// through `handle_event`; "pressed" and "released" then refer to that frame.
+#[derive(Debug, Clone)]
+pub struct InputState {
+    keys_held: HashSet<Scancode>,
+    keys_pressed: HashSet<Scancode>,
+    keys_released: HashSet<Scancode>,
+    buttons_held: HashSet<MouseButton>,
+    buttons_pressed: HashSet<MouseButton>,
+    buttons_released: HashSet<MouseButton>,
+    mouse_position: Vector2,
+    mouse_delta: Vector2,
+    wheel_delta: Vector2,
+    modifiers: KeyModifiers,
+}
+
+impl Default for InputState {
+    fn default() -> Self {
+        InputState {
+            keys_held: HashSet::new(),
+            keys_pressed: HashSet::new(),
+            keys_released: HashSet::new(),
+            buttons_held: HashSet::new(),
+            buttons_pressed: HashSet::new(),
+            buttons_released: HashSet::new(),
+            mouse_position: Vector2::new(0.0, 0.0),
+            mouse_delta: Vector2::new(0.0, 0.0),
+            wheel_delta: Vector2::new(0.0, 0.0),
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl InputState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reset the per-frame state (pressed/released sets and deltas).
+    pub fn begin_frame(&mut self) {
+        self.keys_pressed.clear();
+        self.keys_released.clear();
+        self.buttons_pressed.clear();
+        self.buttons_released.clear();
+        self.mouse_delta = Vector2::new(0.0, 0.0);
+        self.wheel_delta = Vector2::new(0.0, 0.0);
+    }
+
+    pub fn handle_event(&mut self, event: &Event) {
+        match event {
+            Event::KeyDown {
+                scancode,
+                modifiers,
+                repeat,
+                ..
+            } => {
+                self.modifiers = *modifiers;
+                if !*repeat && self.keys_held.insert(*scancode) {
+                    self.keys_pressed.insert(*scancode);
+                }
+            }
+            Event::KeyUp {
+                scancode,
+                modifiers,
+                ..
+            } => {
+                self.modifiers = *modifiers;
+                if self.keys_held.remove(scancode) {
+                    self.keys_released.insert(*scancode);
+                }
+            }
+            Event::MouseMotion {
+                x, y, xrel, yrel, ..
+            } => {
+                self.mouse_position = Vector2::new(*x, *y);
+                self.mouse_delta =
+                    Vector2::new(self.mouse_delta.x + *xrel, self.mouse_delta.y + *yrel);
+            }
+            Event::MouseButtonDown { button, x, y, .. } => {
+                self.mouse_position = Vector2::new(*x, *y);
+                if self.buttons_held.insert(*button) {
+                    self.buttons_pressed.insert(*button);
+                }
+            }
+            Event::MouseButtonUp { button, x, y, .. } => {
+                self.mouse_position = Vector2::new(*x, *y);
+                if self.buttons_held.remove(button) {
+                    self.buttons_released.insert(*button);
+                }
+            }
+            Event::MouseWheel { x, y, .. } => {
+                self.wheel_delta = Vector2::new(self.wheel_delta.x + *x, self.wheel_delta.y + *y);
+            }
+            // Key and button ups are not delivered to unfocused windows, release
+            // everything so nothing stays stuck down.
+            Event::WindowFocusLost { .. } => {
+                self.keys_released.extend(self.keys_held.drain());
+                self.buttons_released.extend(self.buttons_held.drain());
+                self.modifiers = KeyModifiers::NONE;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn is_key_down(&self, scancode: Scancode) -> bool {
+        self.keys_held.contains(&scancode)
+    }
+
+    pub fn was_key_pressed(&self, scancode: Scancode) -> bool {
+        self.keys_pressed.contains(&scancode)
+    }
+
+    pub fn was_key_released(&self, scancode: Scancode) -> bool {
+        self.keys_released.contains(&scancode)
+    }
+
+    pub fn is_button_down(&self, button: MouseButton) -> bool {
+        self.buttons_held.contains(&button)
+    }
+
+    pub fn was_button_pressed(&self, button: MouseButton) -> bool {
+        self.buttons_pressed.contains(&button)
+    }
+
+    pub fn was_button_released(&self, button: MouseButton) -> bool {
+        self.buttons_released.contains(&button)
+    }
+
+    pub fn mouse_position(&self) -> Vector2 {
+        self.mouse_position
+    }
+
+    pub fn mouse_delta(&self) -> Vector2 {
+        self.mouse_delta
+    }
+
+    pub fn wheel_delta(&self) -> Vector2 {
+        self.wheel_delta
+    }
+
+    pub fn modifiers(&self) -> KeyModifiers {
+        self.modifiers
+    }
+
+    pub fn is_binding_down(&self, binding: &InputBinding) -> bool {
+        match binding {
+            InputBinding::Key(scancode) => self.is_key_down(*scancode),
+            InputBinding::Mouse(button) => self.is_button_down(*button),
+        }
+    }
+
+    pub fn was_binding_pressed(&self, binding: &InputBinding) -> bool {
+        match binding {
+            InputBinding::Key(scancode) => self.was_key_pressed(*scancode),
+            InputBinding::Mouse(button) => self.was_button_pressed(*button),
+        }
+    }
+
+    pub fn was_binding_released(&self, binding: &InputBinding) -> bool {
+        match binding {
+            InputBinding::Key(scancode) => self.was_key_released(*scancode),
+            InputBinding::Mouse(button) => self.was_button_released(*button),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputBinding {
+    Key(Scancode),
+    Mouse(MouseButton),
+}
+
+impl InputBinding {
+    /// Parse a binding such as `Key:Space` or `Mouse:Left`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (kind, name) = text.split_once(':')?;
+        let name = name.trim();
+
+        match kind.trim().to_lowercase().as_str() {
+            "key" => Scancode::from_name(name).map(InputBinding::Key),
+            "mouse" => {
+                let button = match name.to_lowercase().as_str() {
+                    "left" => MouseButton::Left,
+                    "middle" => MouseButton::Middle,
+                    "right" => MouseButton::Right,
+                    "x1" => MouseButton::X1,
+                    "x2" => MouseButton::X2,
+                    _ => MouseButton::Other(name.parse().ok()?),
+                };
+
+                Some(InputBinding::Mouse(button))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Named actions bound to keys and mouse buttons.
+///
+/// The config format has one action per line, with `#` starting a comment:
+///
+/// ```text
+/// jump = Key:Space, Mouse:Right
+/// move_forward = Key:W, Key:Up
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ActionMap {
+    bindings: HashMap<String, Vec<InputBinding>>,
+}
+
+#[allow(dead_code)]
+impl ActionMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load_from_file(path: &str) -> Result<Self, Error> {
+        let text = std::fs::read_to_string(path);
+        if let Err(err) = text {
+            return Err(Error::Io(format!(
+                "Failed to read input config {}: {:?}",
+                path, err
+            )));
+        }
+
+        Self::load_from_str(&text.unwrap())
+    }
+
+    pub fn load_from_str(text: &str) -> Result<Self, Error> {
+        let mut map = ActionMap::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = match line.split_once('#') {
+                Some((content, _comment)) => content,
+                None => line,
+            }
+            .trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let (action, bindings) = match line.split_once('=') {
+                Some(res) => res,
+                None => {
+                    return Err(Error::Parse {
+                        line: index + 1,
+                        message: "Expected `action = bindings`".to_string(),
+                    })
+                }
+            };
+
+            let action = action.trim();
+            if action.is_empty() {
+                return Err(Error::Parse {
+                    line: index + 1,
+                    message: "Action name is empty".to_string(),
+                });
+            }
+
+            for binding in bindings.split(',').map(str::trim).filter(|b| !b.is_empty()) {
+                match InputBinding::parse(binding) {
+                    Some(binding) => map.bind(action, binding),
+                    None => {
+                        return Err(Error::Parse {
+                            line: index + 1,
+                            message: format!("Unknown binding `{}`", binding),
+                        })
+                    }
+                }
+            }
+        }
+
+        Ok(map)
+    }
+
+    pub fn bind(&mut self, action: &str, binding: InputBinding) {
+        let bindings = self.bindings.entry(action.to_string()).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    pub fn unbind_all(&mut self, action: &str) {
+        self.bindings.remove(action);
+    }
+
+    pub fn bindings(&self, action: &str) -> &[InputBinding] {
+        match self.bindings.get(action) {
+            Some(bindings) => bindings,
+            None => &[],
+        }
+    }
+
+    pub fn is_action_down(&self, input: &InputState, action: &str) -> bool {
+        self.bindings(action)
+            .iter()
+            .any(|b| input.is_binding_down(b))
+    }
+
+    pub fn was_action_pressed(&self, input: &InputState, action: &str) -> bool {
+        self.bindings(action)
+            .iter()
+            .any(|b| input.was_binding_pressed(b))
+    }
+
+    pub fn was_action_released(&self, input: &InputState, action: &str) -> bool {
+        self.bindings(action)
+            .iter()
+            .any(|b| input.was_binding_released(b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Keycode;
+
+    const KEY_A: Scancode = Scancode(4);
+    const KEY_B: Scancode = Scancode(5);
+
+    fn key_down(scancode: Scancode, repeat: bool) -> Event {
+        Event::KeyDown {
+            window_id: 1,
+            scancode,
+            keycode: Keycode(0),
+            modifiers: KeyModifiers::LSHIFT,
+            repeat,
+        }
+    }
+
+    fn assert_vec2(v: Vector2, x: f32, y: f32) {
+        let (vx, vy) = (v.x, v.y);
+        assert_eq!((vx, vy), (x, y));
+    }
+
+    fn key_up(scancode: Scancode) -> Event {
+        Event::KeyUp {
+            window_id: 1,
+            scancode,
+            keycode: Keycode(0),
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn key_pressed_held_released() {
+        let mut input = InputState::new();
+
+        input.begin_frame();
+        input.handle_event(&key_down(KEY_A, false));
+        assert!(input.was_key_pressed(KEY_A));
+        assert!(input.is_key_down(KEY_A));
+        assert!(!input.was_key_released(KEY_A));
+        assert_eq!(input.modifiers(), KeyModifiers::LSHIFT);
+
+        // Held keys are only "pressed" in the frame they went down.
+        input.begin_frame();
+        input.handle_event(&key_down(KEY_A, true));
+        assert!(!input.was_key_pressed(KEY_A));
+        assert!(input.is_key_down(KEY_A));
+
+        input.begin_frame();
+        input.handle_event(&key_up(KEY_A));
+        assert!(input.was_key_released(KEY_A));
+        assert!(!input.is_key_down(KEY_A));
+        assert_eq!(input.modifiers(), KeyModifiers::NONE);
+
+        input.begin_frame();
+        assert!(!input.was_key_released(KEY_A));
+    }
+
+    #[test]
+    fn mouse_motion_accumulates_per_frame() {
+        let mut input = InputState::new();
+
+        input.begin_frame();
+        for (x, y) in [(10.0, 20.0), (13.0, 18.0)] {
+            input.handle_event(&Event::MouseMotion {
+                window_id: 1,
+                x,
+                y,
+                xrel: 1.5,
+                yrel: -1.0,
+            });
+        }
+
+        assert_vec2(input.mouse_position(), 13.0, 18.0);
+        assert_vec2(input.mouse_delta(), 3.0, -2.0);
+
+        input.begin_frame();
+        assert_vec2(input.mouse_position(), 13.0, 18.0);
+        assert_vec2(input.mouse_delta(), 0.0, 0.0);
+    }
+
+    #[test]
+    fn focus_lost_releases_everything() {
+        let mut input = InputState::new();
+
+        input.begin_frame();
+        input.handle_event(&key_down(KEY_A, false));
+        input.handle_event(&key_down(KEY_B, false));
+
+        input.begin_frame();
+        input.handle_event(&Event::WindowFocusLost { window_id: 1 });
+        assert!(!input.is_key_down(KEY_A));
+        assert!(!input.is_key_down(KEY_B));
+        assert!(input.was_key_released(KEY_A));
+        assert!(input.was_key_released(KEY_B));
+        assert_eq!(input.modifiers(), KeyModifiers::NONE);
+    }
+
+    #[test]
+    fn action_map_parses_bindings() {
+        let map = ActionMap::load_from_str(
+            "# comment\n\
+             fire = Mouse:Left, Mouse:X1 # trailing comment\n\
+             \n\
+             aim = mouse:right\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            map.bindings("fire"),
+            &[
+                InputBinding::Mouse(MouseButton::Left),
+                InputBinding::Mouse(MouseButton::X1)
+            ]
+        );
+        assert_eq!(
+            map.bindings("aim"),
+            &[InputBinding::Mouse(MouseButton::Right)]
+        );
+        assert!(map.bindings("jump").is_empty());
+    }
+
+    #[test]
+    fn action_map_reports_malformed_lines() {
+        let err = ActionMap::load_from_str("fire = Mouse:Left\njump Mouse:Right").unwrap_err();
+        assert!(matches!(err, Error::Parse { line: 2, .. }));
+
+        let err = ActionMap::load_from_str(" = Mouse:Left").unwrap_err();
+        assert!(matches!(err, Error::Parse { line: 1, .. }));
+
+        let err = ActionMap::load_from_str("fire = Joystick:Left").unwrap_err();
+        assert!(matches!(err, Error::Parse { line: 1, .. }));
+    }
+
+    #[test]
+    fn actions_follow_input_state() {
+        let mut map = ActionMap::new();
+        map.bind("jump", InputBinding::Key(KEY_A));
+        map.bind("jump", InputBinding::Mouse(MouseButton::Right));
+
+        let mut input = InputState::new();
+        input.begin_frame();
+        input.handle_event(&Event::MouseButtonDown {
+            window_id: 1,
+            button: MouseButton::Right,
+            clicks: 1,
+            x: 0.0,
+            y: 0.0,
+        });
+
+        assert!(map.was_action_pressed(&input, "jump"));
+        assert!(map.is_action_down(&input, "jump"));
+        assert!(!map.is_action_down(&input, "fire"));
+    }
+}
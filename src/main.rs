@@ -2,19 +2,37 @@ use std::mem::offset_of;
 use std::sync::Arc;
 
 mod buffer;
+mod capture;
 mod command_buffer;
 mod copypass;
+mod cursor;
 mod device;
+mod display;
+mod error;
+mod event;
 mod fence;
+mod frame;
+mod gamepad;
+mod gpu_layout;
+mod input;
 mod math;
 mod pipeline;
+mod readback;
 mod renderpass;
+mod sdl;
 mod shader;
 mod texture;
 mod texture_utils;
 mod window;
 
+use event::Event;
+use event::Scancode;
+use frame::FrameContext;
+use gamepad::GamepadButton;
+use gamepad::GamepadManager;
+use input::InputState;
 use math::Color;
+use math::Mat4;
 use math::Vertex;
 use pipeline::GPUColorBlendFactor;
 use pipeline::GPUColorBlendOp;
@@ -26,11 +44,33 @@ use pipeline::GPUVertexAttribute;
 use pipeline::GPUVertexBufferDescription;
 use pipeline::GPUVertexElement;
 use pipeline::GPUVertexInputRate;
+use sdl::Sdl;
+use sdl::SdlCreateInfo;
 use shader::GPUShaderType;
-use window::Window;
 
 fn main() {
-    let window = Window::new("Hello, World!", 800, 600);
+    let sdl = Sdl::new(&SdlCreateInfo {
+        app_name: "SDL3 GPU Rust PoC".to_string(),
+        gamepad: true,
+        ..Default::default()
+    });
+
+    if let Err(err) = sdl {
+        panic!("Failed to initialize SDL: {:?}", err);
+    }
+
+    let sdl = sdl.unwrap();
+    let window = sdl
+        .window_builder("Hello, World!", 800, 600)
+        .resizable(true)
+        .high_pixel_density(true)
+        .centered()
+        .build();
+    if let Err(err) = window {
+        panic!("Failed to create window: {:?}", err);
+    }
+
+    let window = window.unwrap();
     let gpu = window.create_gpu_device();
 
     if let Err(err) = gpu {
@@ -64,8 +104,9 @@ fn main() {
     ];
 
     // flip the y-axis
+    let flip = Mat4::from_scale(math::Vector3::new(1.0, -1.0, 1.0));
     for vertex in vertices.iter_mut() {
-        vertex.position.y *= -1.0;
+        vertex.position = flip.transform_point(vertex.position);
     }
 
     let indices = vec![0, 1, 2, 2, 3, 0];
@@ -124,8 +165,13 @@ fn main() {
     let vertex_shader = vertex_shader.unwrap();
     let fragment_shader = fragment_shader.unwrap();
 
+    let swapchain_format = gpu.get_swapchain_format();
+    if let Err(err) = swapchain_format {
+        panic!("Failed to get swapchain format: {:?}", err);
+    }
+
     let pipeline_create_info = GPUGraphicsPipelineCreateInfo {
-        texture_format: gpu.get_swapchain_format(),
+        texture_format: swapchain_format.unwrap(),
         vertex_shader: Arc::clone(&vertex_shader),
         fragment_shader: Arc::clone(&fragment_shader),
         vertex_attributes: vec![
@@ -190,8 +236,61 @@ fn main() {
     let texture = texture.unwrap();
     let texture = texture.lock().unwrap();
 
-    while !window.should_close() {
-        let command_buffer = gpu.acquire_command_buffer();
+    let frames = FrameContext::new(&gpu, 2, 1024 * 1024);
+    if let Err(err) = frames {
+        panic!("Failed to create frame context: {:?}", err);
+    }
+
+    let mut frames = frames.unwrap();
+
+    let mut event_pump = sdl.event_pump();
+    let mut input = InputState::new();
+    let escape = Scancode::from_name("Escape");
+
+    let gamepads = GamepadManager::new(&sdl);
+    if let Err(err) = gamepads {
+        panic!("Failed to create gamepad manager: {:?}", err);
+    }
+
+    let mut gamepads = gamepads.unwrap();
+
+    'running: loop {
+        input.begin_frame();
+
+        for event in event_pump.poll_iter() {
+            input.handle_event(&event);
+            if let Err(err) = gamepads.handle_event(&event) {
+                println!("Failed to open gamepad: {:?}", err);
+            }
+
+            if let Err(err) = gpu.handle_event(&event) {
+                panic!("Failed to handle resize: {:?}", err);
+            }
+
+            match event {
+                Event::Quit => break 'running,
+                Event::WindowCloseRequested { window_id } if window_id == window.id() => {
+                    break 'running
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(escape) = escape {
+            if input.was_key_pressed(escape) {
+                break 'running;
+            }
+        }
+
+        gamepads.update();
+        if gamepads
+            .iter()
+            .any(|pad| pad.state().was_button_pressed(GamepadButton::Back))
+        {
+            break 'running;
+        }
+
+        let command_buffer = frames.begin_frame();
         if let Err(_err) = command_buffer {
             continue;
         }
@@ -199,6 +298,7 @@ fn main() {
         let mut command_buffer = command_buffer.unwrap();
         let swapchain = gpu.acquire_swapchain(&mut command_buffer);
         if let Err(_err) = swapchain {
+            let _ = frames.cancel_frame(command_buffer);
             continue;
         }
 
@@ -223,7 +323,7 @@ fn main() {
             }
         }
 
-        if let Err(msg) = command_buffer.submit() {
+        if let Err(msg) = frames.end_frame(command_buffer) {
             println!("Failed to submit command buffer: {:?}", msg);
         }
     }
@@ -1,7 +1,9 @@
 #![allow(dead_code)]
 
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
 #[repr(C, packed)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Vector2 {
     pub x: f32,
     pub y: f32,
@@ -14,7 +16,7 @@ impl Vector2 {
 }
 
 #[repr(C, packed)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Vector3 {
     pub x: f32,
     pub y: f32,
@@ -28,7 +30,7 @@ impl Vector3 {
 }
 
 #[repr(C, packed)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Vector4 {
     pub x: f32,
     pub y: f32,
@@ -42,6 +44,229 @@ impl Vector4 {
     }
 }
 
+impl Vector2 {
+    /// Counter-clockwise perpendicular vector.
+    pub fn perp(self) -> Self {
+        Vector2::new(-self.y, self.x)
+    }
+
+    /// Z component of the 3D cross product, positive if `other` is
+    /// counter-clockwise from `self`.
+    pub fn perp_dot(self, other: Self) -> f32 {
+        self.x * other.y - self.y * other.x
+    }
+
+    pub fn extend(self, z: f32) -> Vector3 {
+        Vector3::new(self.x, self.y, z)
+    }
+}
+
+impl Vector3 {
+    pub fn cross(self, other: Self) -> Self {
+        Vector3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    pub fn extend(self, w: f32) -> Vector4 {
+        Vector4::new(self.x, self.y, self.z, w)
+    }
+
+    pub fn truncate(self) -> Vector2 {
+        Vector2::new(self.x, self.y)
+    }
+}
+
+impl Vector4 {
+    pub fn truncate(self) -> Vector3 {
+        Vector3::new(self.x, self.y, self.z)
+    }
+}
+
+/// Component-wise operators and geometric helpers shared by the vector types.
+macro_rules! impl_vector {
+    ($name:ident, $n:literal, $($field:ident),+) => {
+        impl $name {
+            pub const ZERO: $name = $name { $($field: 0.0),+ };
+            pub const ONE: $name = $name { $($field: 1.0),+ };
+
+            pub fn splat(value: f32) -> Self {
+                $name { $($field: value),+ }
+            }
+
+            pub fn dot(self, other: Self) -> f32 {
+                0.0 $(+ self.$field * other.$field)+
+            }
+
+            pub fn length_squared(self) -> f32 {
+                self.dot(self)
+            }
+
+            pub fn length(self) -> f32 {
+                self.length_squared().sqrt()
+            }
+
+            /// Unit vector in the same direction, or zero for a zero vector.
+            pub fn normalize(self) -> Self {
+                let len = self.length();
+                if len == 0.0 {
+                    return Self::ZERO;
+                }
+
+                self / len
+            }
+
+            pub fn distance(self, other: Self) -> f32 {
+                (other - self).length()
+            }
+
+            pub fn lerp(self, other: Self, t: f32) -> Self {
+                self + (other - self) * t
+            }
+
+            pub fn min(self, other: Self) -> Self {
+                $name { $($field: self.$field.min(other.$field)),+ }
+            }
+
+            pub fn max(self, other: Self) -> Self {
+                $name { $($field: self.$field.max(other.$field)),+ }
+            }
+
+            pub fn clamp(self, min: Self, max: Self) -> Self {
+                self.max(min).min(max)
+            }
+
+            /// Reflect off a surface with the given normal, which must be normalized.
+            pub fn reflect(self, normal: Self) -> Self {
+                self - normal * (2.0 * self.dot(normal))
+            }
+
+            pub fn to_array(self) -> [f32; $n] {
+                [$(self.$field),+]
+            }
+        }
+
+        impl From<[f32; $n]> for $name {
+            fn from(value: [f32; $n]) -> Self {
+                let [$($field),+] = value;
+                $name { $($field),+ }
+            }
+        }
+
+        impl From<$name> for [f32; $n] {
+            fn from(value: $name) -> Self {
+                value.to_array()
+            }
+        }
+
+        impl Add for $name {
+            type Output = $name;
+
+            fn add(self, rhs: $name) -> $name {
+                $name { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl Sub for $name {
+            type Output = $name;
+
+            fn sub(self, rhs: $name) -> $name {
+                $name { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl Mul for $name {
+            type Output = $name;
+
+            fn mul(self, rhs: $name) -> $name {
+                $name { $($field: self.$field * rhs.$field),+ }
+            }
+        }
+
+        impl Div for $name {
+            type Output = $name;
+
+            fn div(self, rhs: $name) -> $name {
+                $name { $($field: self.$field / rhs.$field),+ }
+            }
+        }
+
+        impl Mul<f32> for $name {
+            type Output = $name;
+
+            fn mul(self, rhs: f32) -> $name {
+                $name { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl Mul<$name> for f32 {
+            type Output = $name;
+
+            fn mul(self, rhs: $name) -> $name {
+                rhs * self
+            }
+        }
+
+        impl Div<f32> for $name {
+            type Output = $name;
+
+            fn div(self, rhs: f32) -> $name {
+                $name { $($field: self.$field / rhs),+ }
+            }
+        }
+
+        impl Neg for $name {
+            type Output = $name;
+
+            fn neg(self) -> $name {
+                $name { $($field: -self.$field),+ }
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: $name) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: $name) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl MulAssign for $name {
+            fn mul_assign(&mut self, rhs: $name) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl DivAssign for $name {
+            fn div_assign(&mut self, rhs: $name) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl MulAssign<f32> for $name {
+            fn mul_assign(&mut self, rhs: f32) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl DivAssign<f32> for $name {
+            fn div_assign(&mut self, rhs: f32) {
+                *self = *self / rhs;
+            }
+        }
+    };
+}
+
+impl_vector!(Vector2, 2, x, y);
+impl_vector!(Vector3, 3, x, y, z);
+impl_vector!(Vector4, 4, x, y, z, w);
+
 #[repr(C, packed)]
 #[derive(Debug, Clone, Copy)]
 pub struct Color {
@@ -112,3 +337,1098 @@ pub struct Vertex {
     pub color: Color,
     pub texcoord: Vector2,
 }
+
+/// Column-major 3x3 matrix, usable as a 2D affine transform or a 3D rotation.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat3 {
+    pub cols: [[f32; 3]; 3],
+}
+
+impl Mat3 {
+    pub const IDENTITY: Mat3 = Mat3 {
+        cols: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+    };
+
+    pub const ZERO: Mat3 = Mat3 {
+        cols: [[0.0; 3]; 3],
+    };
+
+    pub fn from_cols(x: Vector3, y: Vector3, z: Vector3) -> Self {
+        Mat3 {
+            cols: [[x.x, x.y, x.z], [y.x, y.y, y.z], [z.x, z.y, z.z]],
+        }
+    }
+
+    /// The upper-left 3x3 part of `m`, dropping translation.
+    pub fn from_mat4(m: &Mat4) -> Self {
+        let c = &m.cols;
+
+        Mat3 {
+            cols: [
+                [c[0][0], c[0][1], c[0][2]],
+                [c[1][0], c[1][1], c[1][2]],
+                [c[2][0], c[2][1], c[2][2]],
+            ],
+        }
+    }
+
+    /// 2D translation for use with homogeneous `(x, y, 1)` vectors.
+    pub fn from_translation(translation: Vector2) -> Self {
+        let mut res = Self::IDENTITY;
+        res.cols[2][0] = translation.x;
+        res.cols[2][1] = translation.y;
+
+        res
+    }
+
+    /// 2D counter-clockwise rotation in radians.
+    pub fn from_rotation(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+
+        Mat3 {
+            cols: [[cos, sin, 0.0], [-sin, cos, 0.0], [0.0, 0.0, 1.0]],
+        }
+    }
+
+    pub fn from_scale(scale: Vector2) -> Self {
+        let mut res = Self::IDENTITY;
+        res.cols[0][0] = scale.x;
+        res.cols[1][1] = scale.y;
+
+        res
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut res = Self::ZERO;
+        for c in 0..3 {
+            for r in 0..3 {
+                res.cols[c][r] = self.cols[r][c];
+            }
+        }
+
+        res
+    }
+
+    pub fn determinant(&self) -> f32 {
+        let [a, b, c] = self.cols;
+
+        a[0] * (b[1] * c[2] - c[1] * b[2]) - b[0] * (a[1] * c[2] - c[1] * a[2])
+            + c[0] * (a[1] * b[2] - b[1] * a[2])
+    }
+
+    /// `None` if the matrix is singular.
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+
+        let [a, b, c] = self.cols;
+        let inv_det = 1.0 / det;
+
+        // Rows of the inverse are the cross products of the columns.
+        let r0 = [
+            b[1] * c[2] - b[2] * c[1],
+            b[2] * c[0] - b[0] * c[2],
+            b[0] * c[1] - b[1] * c[0],
+        ];
+        let r1 = [
+            c[1] * a[2] - c[2] * a[1],
+            c[2] * a[0] - c[0] * a[2],
+            c[0] * a[1] - c[1] * a[0],
+        ];
+        let r2 = [
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+        ];
+
+        let mut res = Self::ZERO;
+        for i in 0..3 {
+            res.cols[i] = [r0[i] * inv_det, r1[i] * inv_det, r2[i] * inv_det];
+        }
+
+        Some(res)
+    }
+
+    pub fn mul_vec3(&self, v: Vector3) -> Vector3 {
+        let c = &self.cols;
+
+        Vector3::new(
+            c[0][0] * v.x + c[1][0] * v.y + c[2][0] * v.z,
+            c[0][1] * v.x + c[1][1] * v.y + c[2][1] * v.z,
+            c[0][2] * v.x + c[1][2] * v.y + c[2][2] * v.z,
+        )
+    }
+
+    pub fn transform_point(&self, p: Vector2) -> Vector2 {
+        let res = self.mul_vec3(Vector3::new(p.x, p.y, 1.0));
+
+        Vector2::new(res.x, res.y)
+    }
+}
+
+impl Default for Mat3 {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mul for Mat3 {
+    type Output = Mat3;
+
+    fn mul(self, rhs: Mat3) -> Mat3 {
+        let mut res = Mat3::ZERO;
+        for c in 0..3 {
+            let col = rhs.cols[c];
+            let v = self.mul_vec3(Vector3::new(col[0], col[1], col[2]));
+            res.cols[c] = [v.x, v.y, v.z];
+        }
+
+        res
+    }
+}
+
+impl Mul<Vector3> for Mat3 {
+    type Output = Vector3;
+
+    fn mul(self, rhs: Vector3) -> Vector3 {
+        self.mul_vec3(rhs)
+    }
+}
+
+/// Column-major 4x4 matrix, laid out the way GLSL `mat4` expects.
+///
+/// Projections follow SDL GPU conventions: right-handed view space looking down
+/// -Z, clip space Y up and depth in the 0..1 range.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat4 {
+    pub cols: [[f32; 4]; 4],
+}
+
+impl Mat4 {
+    pub const IDENTITY: Mat4 = Mat4 {
+        cols: [
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ],
+    };
+
+    pub const ZERO: Mat4 = Mat4 {
+        cols: [[0.0; 4]; 4],
+    };
+
+    pub fn from_cols(x: Vector4, y: Vector4, z: Vector4, w: Vector4) -> Self {
+        Mat4 {
+            cols: [
+                [x.x, x.y, x.z, x.w],
+                [y.x, y.y, y.z, y.w],
+                [z.x, z.y, z.z, z.w],
+                [w.x, w.y, w.z, w.w],
+            ],
+        }
+    }
+
+    pub fn from_mat3(m: &Mat3) -> Self {
+        let c = &m.cols;
+
+        Mat4 {
+            cols: [
+                [c[0][0], c[0][1], c[0][2], 0.0],
+                [c[1][0], c[1][1], c[1][2], 0.0],
+                [c[2][0], c[2][1], c[2][2], 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    /// The matrix as 16 floats in column-major order, ready to upload.
+    pub fn to_cols_array(self) -> [f32; 16] {
+        let mut res = [0.0; 16];
+        for c in 0..4 {
+            res[c * 4..c * 4 + 4].copy_from_slice(&self.cols[c]);
+        }
+
+        res
+    }
+
+    pub fn from_translation(translation: Vector3) -> Self {
+        let mut res = Self::IDENTITY;
+        res.cols[3] = [translation.x, translation.y, translation.z, 1.0];
+
+        res
+    }
+
+    pub fn from_scale(scale: Vector3) -> Self {
+        let mut res = Self::IDENTITY;
+        res.cols[0][0] = scale.x;
+        res.cols[1][1] = scale.y;
+        res.cols[2][2] = scale.z;
+
+        res
+    }
+
+    /// Rotation around the X axis, counter-clockwise in radians.
+    pub fn from_rotation_x(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let mut res = Self::IDENTITY;
+        res.cols[1] = [0.0, cos, sin, 0.0];
+        res.cols[2] = [0.0, -sin, cos, 0.0];
+
+        res
+    }
+
+    pub fn from_rotation_y(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let mut res = Self::IDENTITY;
+        res.cols[0] = [cos, 0.0, -sin, 0.0];
+        res.cols[2] = [sin, 0.0, cos, 0.0];
+
+        res
+    }
+
+    pub fn from_rotation_z(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let mut res = Self::IDENTITY;
+        res.cols[0] = [cos, sin, 0.0, 0.0];
+        res.cols[1] = [-sin, cos, 0.0, 0.0];
+
+        res
+    }
+
+    /// Rotation of `angle` radians around `axis`, which must be normalized.
+    pub fn from_axis_angle(axis: Vector3, angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let t = 1.0 - cos;
+        let (x, y, z) = (axis.x, axis.y, axis.z);
+
+        Mat4 {
+            cols: [
+                [
+                    t * x * x + cos,
+                    t * x * y + sin * z,
+                    t * x * z - sin * y,
+                    0.0,
+                ],
+                [
+                    t * x * y - sin * z,
+                    t * y * y + cos,
+                    t * y * z + sin * x,
+                    0.0,
+                ],
+                [
+                    t * x * z + sin * y,
+                    t * y * z - sin * x,
+                    t * z * z + cos,
+                    0.0,
+                ],
+                [0.0, 0.0, 0.0, 1.0],
+            ],
+        }
+    }
+
+    /// View matrix for a camera at `eye` looking at `target`. When the view
+    /// direction is parallel to `up`, another up axis is picked instead.
+    pub fn look_at(eye: Vector3, target: Vector3, up: Vector3) -> Self {
+        let f = (target - eye).normalize();
+        let s = f.cross(fallback_up(f, up)).normalize();
+        let u = s.cross(f);
+
+        Mat4 {
+            cols: [
+                [s.x, u.x, -f.x, 0.0],
+                [s.y, u.y, -f.y, 0.0],
+                [s.z, u.z, -f.z, 0.0],
+                [-s.dot(eye), -u.dot(eye), f.dot(eye), 1.0],
+            ],
+        }
+    }
+
+    /// Perspective projection with a vertical field of view in radians, mapping
+    /// `near` to depth 0 and `far` to depth 1.
+    pub fn perspective(fov_y: f32, aspect: f32, near: f32, far: f32) -> Self {
+        let f = 1.0 / (fov_y * 0.5).tan();
+        let range = 1.0 / (near - far);
+
+        Mat4 {
+            cols: [
+                [f / aspect, 0.0, 0.0, 0.0],
+                [0.0, f, 0.0, 0.0],
+                [0.0, 0.0, far * range, -1.0],
+                [0.0, 0.0, near * far * range, 0.0],
+            ],
+        }
+    }
+
+    /// Orthographic projection mapping `near` to depth 0 and `far` to depth 1.
+    pub fn orthographic(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Self {
+        let rl = 1.0 / (right - left);
+        let tb = 1.0 / (top - bottom);
+        let range = 1.0 / (near - far);
+
+        Mat4 {
+            cols: [
+                [2.0 * rl, 0.0, 0.0, 0.0],
+                [0.0, 2.0 * tb, 0.0, 0.0],
+                [0.0, 0.0, range, 0.0],
+                [
+                    -(right + left) * rl,
+                    -(top + bottom) * tb,
+                    near * range,
+                    1.0,
+                ],
+            ],
+        }
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut res = Self::ZERO;
+        for c in 0..4 {
+            for r in 0..4 {
+                res.cols[c][r] = self.cols[r][c];
+            }
+        }
+
+        res
+    }
+
+    pub fn determinant(&self) -> f32 {
+        let (s, c) = self.cofactor_terms();
+
+        determinant_from_terms(&s, &c)
+    }
+
+    /// `None` if the matrix is singular.
+    pub fn inverse(&self) -> Option<Self> {
+        let (s, c) = self.cofactor_terms();
+        let det = determinant_from_terms(&s, &c);
+
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+
+        let inv_det = 1.0 / det;
+        let m = self.transpose().cols;
+
+        // Adjugate of the row-major view `m`, built from the 2x2 sub-determinants.
+        let adj = [
+            [
+                m[1][1] * c[5] - m[1][2] * c[4] + m[1][3] * c[3],
+                -m[0][1] * c[5] + m[0][2] * c[4] - m[0][3] * c[3],
+                m[3][1] * s[5] - m[3][2] * s[4] + m[3][3] * s[3],
+                -m[2][1] * s[5] + m[2][2] * s[4] - m[2][3] * s[3],
+            ],
+            [
+                -m[1][0] * c[5] + m[1][2] * c[2] - m[1][3] * c[1],
+                m[0][0] * c[5] - m[0][2] * c[2] + m[0][3] * c[1],
+                -m[3][0] * s[5] + m[3][2] * s[2] - m[3][3] * s[1],
+                m[2][0] * s[5] - m[2][2] * s[2] + m[2][3] * s[1],
+            ],
+            [
+                m[1][0] * c[4] - m[1][1] * c[2] + m[1][3] * c[0],
+                -m[0][0] * c[4] + m[0][1] * c[2] - m[0][3] * c[0],
+                m[3][0] * s[4] - m[3][1] * s[2] + m[3][3] * s[0],
+                -m[2][0] * s[4] + m[2][1] * s[2] - m[2][3] * s[0],
+            ],
+            [
+                -m[1][0] * c[3] + m[1][1] * c[1] - m[1][2] * c[0],
+                m[0][0] * c[3] - m[0][1] * c[1] + m[0][2] * c[0],
+                -m[3][0] * s[3] + m[3][1] * s[1] - m[3][2] * s[0],
+                m[2][0] * s[3] - m[2][1] * s[1] + m[2][2] * s[0],
+            ],
+        ];
+
+        let mut res = Self::ZERO;
+        for (r, row) in adj.iter().enumerate() {
+            for (c, value) in row.iter().enumerate() {
+                res.cols[c][r] = value * inv_det;
+            }
+        }
+
+        Some(res)
+    }
+
+    /// 2x2 sub-determinants of the top two and bottom two rows.
+    fn cofactor_terms(&self) -> ([f32; 6], [f32; 6]) {
+        let m = self.transpose().cols;
+
+        let s = [
+            m[0][0] * m[1][1] - m[1][0] * m[0][1],
+            m[0][0] * m[1][2] - m[1][0] * m[0][2],
+            m[0][0] * m[1][3] - m[1][0] * m[0][3],
+            m[0][1] * m[1][2] - m[1][1] * m[0][2],
+            m[0][1] * m[1][3] - m[1][1] * m[0][3],
+            m[0][2] * m[1][3] - m[1][2] * m[0][3],
+        ];
+
+        let c = [
+            m[2][0] * m[3][1] - m[3][0] * m[2][1],
+            m[2][0] * m[3][2] - m[3][0] * m[2][2],
+            m[2][0] * m[3][3] - m[3][0] * m[2][3],
+            m[2][1] * m[3][2] - m[3][1] * m[2][2],
+            m[2][1] * m[3][3] - m[3][1] * m[2][3],
+            m[2][2] * m[3][3] - m[3][2] * m[2][3],
+        ];
+
+        (s, c)
+    }
+
+    pub fn mul_vec4(&self, v: Vector4) -> Vector4 {
+        let c = &self.cols;
+        let v = [v.x, v.y, v.z, v.w];
+        let mut res = [0.0; 4];
+        for (r, out) in res.iter_mut().enumerate() {
+            *out = c[0][r] * v[0] + c[1][r] * v[1] + c[2][r] * v[2] + c[3][r] * v[3];
+        }
+
+        Vector4::new(res[0], res[1], res[2], res[3])
+    }
+
+    /// Transform a position, applying translation and the perspective divide.
+    pub fn transform_point(&self, p: Vector3) -> Vector3 {
+        let res = self.mul_vec4(Vector4::new(p.x, p.y, p.z, 1.0));
+        let w = if res.w != 0.0 { 1.0 / res.w } else { 1.0 };
+
+        Vector3::new(res.x * w, res.y * w, res.z * w)
+    }
+
+    /// Transform a direction, ignoring translation.
+    pub fn transform_vector(&self, v: Vector3) -> Vector3 {
+        let res = self.mul_vec4(Vector4::new(v.x, v.y, v.z, 0.0));
+
+        Vector3::new(res.x, res.y, res.z)
+    }
+}
+
+impl Default for Mat4 {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mul for Mat4 {
+    type Output = Mat4;
+
+    fn mul(self, rhs: Mat4) -> Mat4 {
+        let mut res = Mat4::ZERO;
+        for c in 0..4 {
+            let col = rhs.cols[c];
+            let v = self.mul_vec4(Vector4::new(col[0], col[1], col[2], col[3]));
+            res.cols[c] = [v.x, v.y, v.z, v.w];
+        }
+
+        res
+    }
+}
+
+impl Mul<Vector4> for Mat4 {
+    type Output = Vector4;
+
+    fn mul(self, rhs: Vector4) -> Vector4 {
+        self.mul_vec4(rhs)
+    }
+}
+
+fn determinant_from_terms(s: &[f32; 6], c: &[f32; 6]) -> f32 {
+    s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0]
+}
+
+/// `up`, or a world axis that is not parallel to `forward` if `up` is.
+fn fallback_up(forward: Vector3, up: Vector3) -> Vector3 {
+    if forward.cross(up).length_squared() > f32::EPSILON * up.length_squared() {
+        return up;
+    }
+
+    if forward.z.abs() < 0.9 {
+        Vector3::new(0.0, 0.0, -1.0)
+    } else {
+        Vector3::new(0.0, 1.0, 0.0)
+    }
+}
+
+/// Unit quaternion representing a 3D rotation.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quat {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+impl Quat {
+    pub const IDENTITY: Quat = Quat {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+        w: 1.0,
+    };
+
+    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Quat { x, y, z, w }
+    }
+
+    /// Rotation of `angle` radians around `axis`, which must be normalized.
+    pub fn from_axis_angle(axis: Vector3, angle: f32) -> Self {
+        let (sin, cos) = (angle * 0.5).sin_cos();
+
+        Quat::new(axis.x * sin, axis.y * sin, axis.z * sin, cos)
+    }
+
+    /// Rotation from Euler angles in radians, applied as roll around Z, then
+    /// pitch around X, then yaw around Y.
+    pub fn from_euler(yaw: f32, pitch: f32, roll: f32) -> Self {
+        Quat::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), yaw)
+            * Quat::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), pitch)
+            * Quat::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), roll)
+    }
+
+    /// Rotation of a pure rotation matrix.
+    pub fn from_mat3(m: &Mat3) -> Self {
+        let [c0, c1, c2] = m.cols;
+        let trace = c0[0] + c1[1] + c2[2];
+
+        let res = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Quat::new(
+                (c1[2] - c2[1]) / s,
+                (c2[0] - c0[2]) / s,
+                (c0[1] - c1[0]) / s,
+                0.25 * s,
+            )
+        } else if c0[0] > c1[1] && c0[0] > c2[2] {
+            let s = (1.0 + c0[0] - c1[1] - c2[2]).sqrt() * 2.0;
+            Quat::new(
+                0.25 * s,
+                (c1[0] + c0[1]) / s,
+                (c2[0] + c0[2]) / s,
+                (c1[2] - c2[1]) / s,
+            )
+        } else if c1[1] > c2[2] {
+            let s = (1.0 + c1[1] - c0[0] - c2[2]).sqrt() * 2.0;
+            Quat::new(
+                (c1[0] + c0[1]) / s,
+                0.25 * s,
+                (c2[1] + c1[2]) / s,
+                (c2[0] - c0[2]) / s,
+            )
+        } else {
+            let s = (1.0 + c2[2] - c0[0] - c1[1]).sqrt() * 2.0;
+            Quat::new(
+                (c2[0] + c0[2]) / s,
+                (c2[1] + c1[2]) / s,
+                0.25 * s,
+                (c0[1] - c1[0]) / s,
+            )
+        };
+
+        res.normalize()
+    }
+
+    /// Rotation that points -Z along `forward` with Y as close to `up` as
+    /// possible, matching the camera convention of `Mat4::look_at`. When
+    /// `forward` is parallel to `up`, another world axis is used as up.
+    pub fn look_rotation(forward: Vector3, up: Vector3) -> Self {
+        let back = -forward.normalize();
+        let right = fallback_up(-back, up).cross(back).normalize();
+        let up = back.cross(right);
+
+        Quat::from_mat3(&Mat3::from_cols(right, up, back))
+    }
+
+    pub fn dot(self, other: Self) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    pub fn length(self) -> f32 {
+        self.dot(self).sqrt()
+    }
+
+    pub fn normalize(self) -> Self {
+        let len = self.length();
+        if len == 0.0 {
+            return Self::IDENTITY;
+        }
+
+        Quat::new(self.x / len, self.y / len, self.z / len, self.w / len)
+    }
+
+    pub fn conjugate(self) -> Self {
+        Quat::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    /// Opposite rotation. Equal to `conjugate` for unit quaternions.
+    pub fn inverse(self) -> Self {
+        let len_squared = self.dot(self);
+        if len_squared == 0.0 {
+            return Self::IDENTITY;
+        }
+
+        let c = self.conjugate();
+        Quat::new(
+            c.x / len_squared,
+            c.y / len_squared,
+            c.z / len_squared,
+            c.w / len_squared,
+        )
+    }
+
+    pub fn rotate(self, v: Vector3) -> Vector3 {
+        let q = Vector3::new(self.x, self.y, self.z);
+        let t = q.cross(v) * 2.0;
+
+        v + t * self.w + q.cross(t)
+    }
+
+    /// Normalized linear interpolation. Cheaper than `slerp` and fine for
+    /// small angles, but the speed is not constant.
+    pub fn nlerp(self, other: Self, t: f32) -> Self {
+        let other = if self.dot(other) < 0.0 { -other } else { other };
+
+        Quat::new(
+            self.x + (other.x - self.x) * t,
+            self.y + (other.y - self.y) * t,
+            self.z + (other.z - self.z) * t,
+            self.w + (other.w - self.w) * t,
+        )
+        .normalize()
+    }
+
+    /// Spherical interpolation along the shortest path.
+    pub fn slerp(self, other: Self, t: f32) -> Self {
+        let mut cos = self.dot(other);
+        let mut other = other;
+        if cos < 0.0 {
+            cos = -cos;
+            other = -other;
+        }
+
+        // Nearly identical rotations make the sine below unstable.
+        if cos > 0.9995 {
+            return self.nlerp(other, t);
+        }
+
+        let angle = cos.acos();
+        let sin = angle.sin();
+        let a = ((1.0 - t) * angle).sin() / sin;
+        let b = (t * angle).sin() / sin;
+
+        Quat::new(
+            self.x * a + other.x * b,
+            self.y * a + other.y * b,
+            self.z * a + other.z * b,
+            self.w * a + other.w * b,
+        )
+    }
+
+    pub fn to_mat3(self) -> Mat3 {
+        let Quat { x, y, z, w } = self;
+        let (x2, y2, z2) = (x + x, y + y, z + z);
+        let (xx, yy, zz) = (x * x2, y * y2, z * z2);
+        let (xy, xz, yz) = (x * y2, x * z2, y * z2);
+        let (wx, wy, wz) = (w * x2, w * y2, w * z2);
+
+        Mat3 {
+            cols: [
+                [1.0 - (yy + zz), xy + wz, xz - wy],
+                [xy - wz, 1.0 - (xx + zz), yz + wx],
+                [xz + wy, yz - wx, 1.0 - (xx + yy)],
+            ],
+        }
+    }
+
+    pub fn to_mat4(self) -> Mat4 {
+        Mat4::from_mat3(&self.to_mat3())
+    }
+}
+
+impl Default for Quat {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Combined rotation applying `rhs` first, then `self`.
+impl Mul for Quat {
+    type Output = Quat;
+
+    fn mul(self, rhs: Quat) -> Quat {
+        Quat::new(
+            self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+            self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+        )
+    }
+}
+
+impl Mul<Vector3> for Quat {
+    type Output = Vector3;
+
+    fn mul(self, rhs: Vector3) -> Vector3 {
+        self.rotate(rhs)
+    }
+}
+
+impl Neg for Quat {
+    type Output = Quat;
+
+    fn neg(self) -> Quat {
+        Quat::new(-self.x, -self.y, -self.z, -self.w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOLERANCE: f32 = 1e-4;
+
+    fn assert_mat4_eq(a: &Mat4, b: &Mat4) {
+        for c in 0..4 {
+            for r in 0..4 {
+                assert!(
+                    (a.cols[c][r] - b.cols[c][r]).abs() < TOLERANCE,
+                    "{:?} != {:?}",
+                    a,
+                    b
+                );
+            }
+        }
+    }
+
+    fn assert_mat3_eq(a: &Mat3, b: &Mat3) {
+        for c in 0..3 {
+            for r in 0..3 {
+                assert!(
+                    (a.cols[c][r] - b.cols[c][r]).abs() < TOLERANCE,
+                    "{:?} != {:?}",
+                    a,
+                    b
+                );
+            }
+        }
+    }
+
+    fn assert_vec3_eq(a: Vector3, b: Vector3) {
+        let (a, b) = ([a.x, a.y, a.z], [b.x, b.y, b.z]);
+        for i in 0..3 {
+            assert!((a[i] - b[i]).abs() < TOLERANCE, "{:?} != {:?}", a, b);
+        }
+    }
+
+    /// Depth of `point` after projection and perspective divide.
+    fn project_depth(proj: &Mat4, point: Vector3) -> f32 {
+        let clip = proj.mul_vec4(Vector4::new(point.x, point.y, point.z, 1.0));
+
+        clip.z / clip.w
+    }
+
+    #[test]
+    fn mat4_inverse_round_trip() {
+        let m = Mat4::from_translation(Vector3::new(1.0, -2.0, 3.0))
+            * Mat4::from_axis_angle(Vector3::new(0.0, 0.6, 0.8), 0.7)
+            * Mat4::from_scale(Vector3::new(2.0, 0.5, 3.0));
+
+        let inv = m.inverse().unwrap();
+        assert_mat4_eq(&(m * inv), &Mat4::IDENTITY);
+        assert_mat4_eq(&(inv * m), &Mat4::IDENTITY);
+    }
+
+    #[test]
+    fn mat3_inverse_round_trip() {
+        let m = Mat3::from_translation(Vector2::new(4.0, -1.0))
+            * Mat3::from_rotation(1.2)
+            * Mat3::from_scale(Vector2::new(3.0, 0.25));
+
+        let inv = m.inverse().unwrap();
+        assert_mat3_eq(&(m * inv), &Mat3::IDENTITY);
+    }
+
+    #[test]
+    fn inverse_of_small_scale() {
+        let m = Mat4::from_scale(Vector3::new(0.001, 0.001, 0.001));
+        let inv = m.inverse().unwrap();
+        assert_mat4_eq(&(m * inv), &Mat4::IDENTITY);
+
+        let m = Mat3::from_scale(Vector2::new(0.0001, 0.0001));
+        let inv = m.inverse().unwrap();
+        assert_mat3_eq(&(m * inv), &Mat3::IDENTITY);
+    }
+
+    #[test]
+    fn inverse_of_singular_matrix() {
+        assert!(Mat4::ZERO.inverse().is_none());
+        assert!(Mat4::from_scale(Vector3::new(1.0, 0.0, 1.0))
+            .inverse()
+            .is_none());
+        assert!(Mat3::ZERO.inverse().is_none());
+    }
+
+    #[test]
+    fn transpose() {
+        let m = Mat4::from_cols(
+            Vector4::new(1.0, 2.0, 3.0, 4.0),
+            Vector4::new(5.0, 6.0, 7.0, 8.0),
+            Vector4::new(9.0, 10.0, 11.0, 12.0),
+            Vector4::new(13.0, 14.0, 15.0, 16.0),
+        );
+
+        let t = m.transpose();
+        assert_eq!(t.cols[0], [1.0, 5.0, 9.0, 13.0]);
+        assert_eq!(t.cols[3], [4.0, 8.0, 12.0, 16.0]);
+        assert_eq!(t.transpose(), m);
+
+        let m = Mat3::from_translation(Vector2::new(2.0, 3.0));
+        assert_eq!(m.transpose().cols[0], [1.0, 0.0, 2.0]);
+    }
+
+    #[test]
+    fn determinant() {
+        assert_eq!(Mat4::IDENTITY.determinant(), 1.0);
+        assert_eq!(
+            Mat4::from_scale(Vector3::new(2.0, 3.0, 4.0)).determinant(),
+            24.0
+        );
+        assert!((Mat4::from_rotation_y(0.3).determinant() - 1.0).abs() < TOLERANCE);
+
+        assert_eq!(Mat3::from_scale(Vector2::new(2.0, 5.0)).determinant(), 10.0);
+        assert!((Mat3::from_rotation(0.3).determinant() - 1.0).abs() < TOLERANCE);
+    }
+
+    #[test]
+    fn look_at() {
+        let eye = Vector3::new(1.0, 2.0, 5.0);
+        let target = Vector3::new(1.0, 2.0, 0.0);
+        let view = Mat4::look_at(eye, target, Vector3::new(0.0, 1.0, 0.0));
+
+        // The eye moves to the origin and the target lies along -Z.
+        assert_vec3_eq(view.transform_point(eye), Vector3::new(0.0, 0.0, 0.0));
+        assert_vec3_eq(view.transform_point(target), Vector3::new(0.0, 0.0, -5.0));
+        assert_vec3_eq(
+            view.transform_vector(Vector3::new(1.0, 0.0, 0.0)),
+            Vector3::new(1.0, 0.0, 0.0),
+        );
+    }
+
+    #[test]
+    fn look_at_along_up() {
+        let eye = Vector3::new(0.0, 10.0, 0.0);
+        let up = Vector3::new(0.0, 1.0, 0.0);
+
+        for target in [Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 20.0, 0.0)] {
+            let view = Mat4::look_at(eye, target, up);
+
+            assert!(view.cols.iter().flatten().all(|v| v.is_finite()));
+            assert!((view.determinant() - 1.0).abs() < TOLERANCE);
+            assert_vec3_eq(view.transform_point(eye), Vector3::new(0.0, 0.0, 0.0));
+            assert_vec3_eq(view.transform_point(target), Vector3::new(0.0, 0.0, -10.0));
+        }
+    }
+
+    #[test]
+    fn perspective_depth_range() {
+        let proj = Mat4::perspective(1.0, 16.0 / 9.0, 0.1, 100.0);
+
+        assert!(project_depth(&proj, Vector3::new(0.0, 0.0, -0.1)).abs() < TOLERANCE);
+        assert!((project_depth(&proj, Vector3::new(0.0, 0.0, -100.0)) - 1.0).abs() < TOLERANCE);
+
+        let mid = project_depth(&proj, Vector3::new(0.0, 0.0, -10.0));
+        assert!(mid > 0.0 && mid < 1.0);
+    }
+
+    #[test]
+    fn orthographic_depth_range() {
+        let proj = Mat4::orthographic(-2.0, 2.0, -1.0, 1.0, 0.5, 10.0);
+
+        assert!(project_depth(&proj, Vector3::new(0.0, 0.0, -0.5)).abs() < TOLERANCE);
+        assert!((project_depth(&proj, Vector3::new(0.0, 0.0, -10.0)) - 1.0).abs() < TOLERANCE);
+
+        let corner = proj.transform_point(Vector3::new(2.0, 1.0, -1.0));
+        assert!((corner.x - 1.0).abs() < TOLERANCE);
+        assert!((corner.y - 1.0).abs() < TOLERANCE);
+    }
+
+    #[test]
+    fn vector_operators() {
+        let a = Vector3::new(1.0, 2.0, 3.0);
+        let b = Vector3::new(4.0, -5.0, 6.0);
+
+        assert_eq!(a + b, Vector3::new(5.0, -3.0, 9.0));
+        assert_eq!(a - b, Vector3::new(-3.0, 7.0, -3.0));
+        assert_eq!(a * b, Vector3::new(4.0, -10.0, 18.0));
+        assert_eq!(b / a, Vector3::new(4.0, -2.5, 2.0));
+        assert_eq!(a * 2.0, 2.0 * a);
+        assert_eq!(a / 2.0, Vector3::new(0.5, 1.0, 1.5));
+        assert_eq!(-a, Vector3::new(-1.0, -2.0, -3.0));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        c *= 2.0;
+        c /= Vector3::splat(2.0);
+        assert_eq!(c, b);
+    }
+
+    #[test]
+    fn vector_dot_and_cross() {
+        let x = Vector3::new(1.0, 0.0, 0.0);
+        let y = Vector3::new(0.0, 1.0, 0.0);
+
+        assert_eq!(x.cross(y), Vector3::new(0.0, 0.0, 1.0));
+        assert_eq!(y.cross(x), Vector3::new(0.0, 0.0, -1.0));
+        assert_eq!(x.dot(y), 0.0);
+        assert_eq!(Vector4::new(1.0, 2.0, 3.0, 4.0).dot(Vector4::ONE), 10.0);
+        assert_eq!(Vector2::new(1.0, 0.0).perp_dot(Vector2::new(0.0, 1.0)), 1.0);
+    }
+
+    #[test]
+    fn vector_length_and_normalize() {
+        let v = Vector2::new(3.0, 4.0);
+
+        assert_eq!(v.length_squared(), 25.0);
+        assert_eq!(v.length(), 5.0);
+        assert_eq!(v.normalize(), Vector2::new(0.6, 0.8));
+        assert_eq!(v.distance(Vector2::ZERO), 5.0);
+        assert!((Vector4::splat(3.0).normalize().length() - 1.0).abs() < TOLERANCE);
+
+        assert_eq!(Vector2::ZERO.normalize(), Vector2::ZERO);
+        assert_eq!(Vector3::ZERO.normalize(), Vector3::ZERO);
+        assert_eq!(Vector4::ZERO.normalize(), Vector4::ZERO);
+    }
+
+    #[test]
+    fn vector_reflect_and_lerp() {
+        let v = Vector2::new(1.0, -1.0);
+        assert_eq!(v.reflect(Vector2::new(0.0, 1.0)), Vector2::new(1.0, 1.0));
+
+        let a = Vector3::new(0.0, 10.0, -2.0);
+        let b = Vector3::new(4.0, 20.0, 2.0);
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert_eq!(a.lerp(b, 0.25), Vector3::new(1.0, 12.5, -1.0));
+    }
+
+    #[test]
+    fn vector_min_max_clamp() {
+        let a = Vector4::new(1.0, 5.0, -2.0, 0.0);
+        let b = Vector4::new(3.0, 2.0, -4.0, 0.0);
+
+        assert_eq!(a.min(b), Vector4::new(1.0, 2.0, -4.0, 0.0));
+        assert_eq!(a.max(b), Vector4::new(3.0, 5.0, -2.0, 0.0));
+        assert_eq!(
+            a.clamp(Vector4::splat(-1.0), Vector4::splat(2.0)),
+            Vector4::new(1.0, 2.0, -1.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn vector_conversions() {
+        let v = Vector3::from([1.0, 2.0, 3.0]);
+        assert_eq!(v, Vector3::new(1.0, 2.0, 3.0));
+        assert_eq!(<[f32; 3]>::from(v), [1.0, 2.0, 3.0]);
+        assert_eq!(Vector2::from([5.0, 6.0]).to_array(), [5.0, 6.0]);
+        assert_eq!(
+            <[f32; 4]>::from(Vector4::new(1.0, 2.0, 3.0, 4.0)),
+            [1.0, 2.0, 3.0, 4.0]
+        );
+
+        assert_eq!(v.extend(4.0), Vector4::new(1.0, 2.0, 3.0, 4.0));
+        assert_eq!(v.extend(4.0).truncate(), v);
+        assert_eq!(v.truncate(), Vector2::new(1.0, 2.0));
+        assert_eq!(Vector2::new(1.0, 2.0).extend(3.0), v);
+    }
+
+    fn assert_quat_eq(a: Quat, b: Quat) {
+        // `q` and `-q` are the same rotation.
+        let b = if a.dot(b) < 0.0 { -b } else { b };
+        let (a, b) = ([a.x, a.y, a.z, a.w], [b.x, b.y, b.z, b.w]);
+        for i in 0..4 {
+            assert!((a[i] - b[i]).abs() < TOLERANCE, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn quat_to_mat4_matches_axis_rotations() {
+        let angle = 0.8;
+
+        assert_mat4_eq(
+            &Quat::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), angle).to_mat4(),
+            &Mat4::from_rotation_x(angle),
+        );
+        assert_mat4_eq(
+            &Quat::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), angle).to_mat4(),
+            &Mat4::from_rotation_y(angle),
+        );
+        assert_mat4_eq(
+            &Quat::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), angle).to_mat4(),
+            &Mat4::from_rotation_z(angle),
+        );
+
+        let axis = Vector3::new(0.48, 0.6, 0.64);
+        assert_mat4_eq(
+            &Quat::from_axis_angle(axis, angle).to_mat4(),
+            &Mat4::from_axis_angle(axis, angle),
+        );
+    }
+
+    #[test]
+    fn quat_from_euler() {
+        let (yaw, pitch, roll) = (0.7, -0.4, 1.1);
+        let q = Quat::from_euler(yaw, pitch, roll);
+
+        let expected =
+            Mat4::from_rotation_y(yaw) * Mat4::from_rotation_x(pitch) * Mat4::from_rotation_z(roll);
+        assert_mat4_eq(&q.to_mat4(), &expected);
+
+        assert_quat_eq(Quat::from_mat3(&q.to_mat3()), q);
+
+        let v = Vector3::new(1.0, 2.0, 3.0);
+        assert_vec3_eq(q * v, expected.transform_vector(v));
+        assert_vec3_eq(q.inverse() * (q * v), v);
+    }
+
+    #[test]
+    fn quat_slerp() {
+        let axis = Vector3::new(0.0, 1.0, 0.0);
+        let a = Quat::from_axis_angle(axis, 0.2);
+        let b = Quat::from_axis_angle(axis, 1.4);
+
+        assert_quat_eq(a.slerp(b, 0.0), a);
+        assert_quat_eq(a.slerp(b, 1.0), b);
+        assert_quat_eq(a.slerp(b, 0.5), Quat::from_axis_angle(axis, 0.8));
+
+        // Takes the shortest path when the inputs are on opposite hemispheres.
+        assert_quat_eq(a.slerp(-b, 0.5), Quat::from_axis_angle(axis, 0.8));
+    }
+
+    #[test]
+    fn quat_look_rotation_matches_look_at() {
+        let eye = Vector3::new(2.0, 1.0, 3.0);
+        let target = Vector3::new(-1.0, 0.5, -2.0);
+        let up = Vector3::new(0.0, 1.0, 0.0);
+
+        let rotation = Quat::look_rotation(target - eye, up).to_mat4();
+        let view = Mat4::look_at(eye, target, up);
+
+        // The view matrix is the inverse of the camera transform.
+        let camera = Mat4::from_translation(eye) * rotation;
+        assert_mat4_eq(&camera.inverse().unwrap(), &view);
+    }
+
+    #[test]
+    fn quat_look_rotation_along_up() {
+        let up = Vector3::new(0.0, 1.0, 0.0);
+
+        for forward in [up, -up] {
+            let q = Quat::look_rotation(forward, up);
+            assert!(q.x.is_finite() && q.y.is_finite() && q.z.is_finite() && q.w.is_finite());
+            assert!((q.length() - 1.0).abs() < TOLERANCE);
+            assert_vec3_eq(q * Vector3::new(0.0, 0.0, -1.0), forward);
+        }
+    }
+}
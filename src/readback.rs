@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use crate::{buffer::GPUTransferBuffer, error::Error, fence::EstFence};
+
+/// A pending GPU to CPU download. The bytes can be taken once the fence
+/// of the submitted copy has signaled, either by polling or by blocking.
+#[derive(Debug)]
+pub struct GPUReadback {
+    fence: Arc<EstFence>,
+    transfer_buffer: Arc<GPUTransferBuffer>,
+    size: usize,
+    taken: bool,
+}
+
+#[allow(dead_code)]
+impl GPUReadback {
+    pub fn new(fence: Arc<EstFence>, transfer_buffer: Arc<GPUTransferBuffer>, size: usize) -> Self {
+        GPUReadback {
+            fence,
+            transfer_buffer,
+            size,
+            taken: false,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.fence.is_ready()
+    }
+
+    /// Block until the GPU has finished the copy and take the bytes.
+    pub fn wait(&mut self) -> Result<Vec<u8>, Error> {
+        if self.taken {
+            return Err(Error::InvalidUsage(
+                "Readback is already taken!".to_string(),
+            ));
+        }
+
+        self.fence.wait();
+        self.take()
+    }
+
+    /// Take the bytes if the GPU has finished the copy, without blocking.
+    /// Returns `Ok(None)` while the copy is still in flight.
+    pub fn try_take(&mut self) -> Result<Option<Vec<u8>>, Error> {
+        if self.taken {
+            return Err(Error::InvalidUsage(
+                "Readback is already taken!".to_string(),
+            ));
+        }
+
+        if !self.fence.is_ready() {
+            return Ok(None);
+        }
+
+        self.take().map(Some)
+    }
+
+    fn take(&mut self) -> Result<Vec<u8>, Error> {
+        let data = self.transfer_buffer.read_mapped(self.size)?;
+        self.taken = true;
+
+        Ok(data)
+    }
+}
@@ -5,7 +5,7 @@ use std::{
 
 use sdl3_sys::{gpu, rect};
 
-use crate::{buffer::GPUBuffer, pipeline::GPUGraphicsPipeline, texture::GPUTexture};
+use crate::{buffer::GPUBuffer, error::Error, pipeline::GPUGraphicsPipeline, texture::GPUTexture};
 
 pub struct GPURenderpass {
     pub renderpass: *mut gpu::SDL_GPURenderPass,
@@ -28,9 +28,9 @@ impl DerefMut for GPURenderpass {
 
 #[allow(dead_code)]
 impl GPURenderpass {
-    pub fn end(&mut self) -> Result<(), String> {
+    pub fn end(&mut self) -> Result<(), Error> {
         if self.used {
-            return Err("Renderpass is already ended!".to_string());
+            return Err(Error::AlreadyEnded);
         }
 
         unsafe {
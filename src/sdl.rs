@@ -0,0 +1,165 @@
+use std::{
+    ffi::CString,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use sdl3_sys::{hints, init};
+
+use crate::{
+    error::Error,
+    event::EventPump,
+    window::{Window, WindowBuilder},
+};
+
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+pub struct SdlCreateInfo {
+    pub app_name: String,
+    pub app_version: String,
+    pub app_identifier: String,
+    pub gamepad: bool,
+    pub audio: bool,
+    pub hints: Vec<(String, String)>,
+}
+
+impl Default for SdlCreateInfo {
+    fn default() -> Self {
+        SdlCreateInfo {
+            app_name: env!("CARGO_PKG_NAME").to_string(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            app_identifier: String::new(),
+            gamepad: false,
+            audio: false,
+            hints: Vec::new(),
+        }
+    }
+}
+
+/// Owns SDL initialization. Video and events are always initialized, gamepad and
+/// audio on request. `SDL_Quit` is called once the context and every window
+/// created from it have been dropped. Only one context may exist at a time.
+#[derive(Debug)]
+pub struct Sdl {
+    flags: init::SDL_InitFlags,
+}
+
+#[allow(dead_code)]
+impl Sdl {
+    pub fn new(info: &SdlCreateInfo) -> Result<Arc<Self>, Error> {
+        if INITIALIZED.swap(true, Ordering::SeqCst) {
+            return Err(Error::InvalidUsage(
+                "SDL is already initialized".to_string(),
+            ));
+        }
+
+        let res = Self::init(info);
+        if res.is_err() {
+            INITIALIZED.store(false, Ordering::SeqCst);
+        }
+
+        res
+    }
+
+    fn init(info: &SdlCreateInfo) -> Result<Arc<Self>, Error> {
+        let name = to_cstring(&info.app_name)?;
+        let version = to_cstring(&info.app_version)?;
+        let identifier = to_cstring(&info.app_identifier)?;
+
+        let identifier_ptr = if info.app_identifier.is_empty() {
+            std::ptr::null()
+        } else {
+            identifier.as_ptr()
+        };
+
+        let res =
+            unsafe { init::SDL_SetAppMetadata(name.as_ptr(), version.as_ptr(), identifier_ptr) };
+        if !res {
+            return Err(Error::sdl("Failed to set app metadata"));
+        }
+
+        // Hints have to be set before the subsystems they affect are initialized.
+        for (name, value) in &info.hints {
+            let name = to_cstring(name)?;
+            let value = to_cstring(value)?;
+
+            unsafe {
+                hints::SDL_SetHint(name.as_ptr(), value.as_ptr());
+            }
+        }
+
+        let mut flags = init::SDL_INIT_VIDEO | init::SDL_INIT_EVENTS;
+        if info.gamepad {
+            flags |= init::SDL_INIT_GAMEPAD;
+        }
+
+        if info.audio {
+            flags |= init::SDL_INIT_AUDIO;
+        }
+
+        let res = unsafe { init::SDL_Init(flags) };
+        if !res {
+            return Err(Error::sdl("Failed to initialize SDL"));
+        }
+
+        Ok(Arc::new(Sdl { flags }))
+    }
+
+    pub fn has_gamepad(&self) -> bool {
+        self.flags & init::SDL_INIT_GAMEPAD != 0
+    }
+
+    pub fn has_audio(&self) -> bool {
+        self.flags & init::SDL_INIT_AUDIO != 0
+    }
+
+    pub fn set_hint(&self, name: &str, value: &str) -> Result<(), Error> {
+        let name = to_cstring(name)?;
+        let value = to_cstring(value)?;
+
+        let res = unsafe { hints::SDL_SetHint(name.as_ptr(), value.as_ptr()) };
+        if !res {
+            return Err(Error::sdl("Failed to set hint"));
+        }
+
+        Ok(())
+    }
+
+    pub fn event_pump(self: &Arc<Self>) -> EventPump {
+        EventPump::new(self)
+    }
+
+    pub fn create_window(
+        self: &Arc<Self>,
+        title: &str,
+        width: i32,
+        height: i32,
+    ) -> Result<Arc<Window>, Error> {
+        Window::new(self, title, width, height)
+    }
+
+    pub fn window_builder(self: &Arc<Self>, title: &str, width: i32, height: i32) -> WindowBuilder {
+        WindowBuilder::new(self, title, width, height)
+    }
+}
+
+fn to_cstring(value: &str) -> Result<CString, Error> {
+    match CString::new(value) {
+        Ok(cstr) => Ok(cstr),
+        Err(_err) => Err(Error::InvalidUsage(
+            "String must not contain a nul byte".to_string(),
+        )),
+    }
+}
+
+impl Drop for Sdl {
+    fn drop(&mut self) {
+        unsafe {
+            init::SDL_Quit();
+        }
+
+        INITIALIZED.store(false, Ordering::SeqCst);
+    }
+}
@@ -7,6 +7,9 @@ use crate::math::Rect;
 pub enum GPUTextureAccess {
     Sampler,
     RenderTarget,
+    /// Render target that can also be sampled and blitted from, e.g. an
+    /// offscreen frame that is presented with `CommandBuffer::blit`.
+    SampledRenderTarget,
     DepthStencil,
     GraphicsStorage,
     ComputeStorageRead,
@@ -14,6 +17,26 @@ pub enum GPUTextureAccess {
     ComputeStorageReadWrite,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub enum GPUFilter {
+    Nearest,
+    Linear,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum GPUFlipMode {
+    None,
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum GPULoadOp {
+    Load,
+    Clear,
+    DontCare,
+}
+
 #[derive(Debug, Clone, Copy)]
 #[allow(non_camel_case_types)]
 pub enum GPUTextureFormat {
@@ -132,6 +155,8 @@ pub struct GPUTexture {
     pub size: Rect,
     pub format: GPUTextureFormat,
     pub access: GPUTextureAccess,
+    pub layer_count: u32,
+    pub num_levels: u32,
 }
 
 impl Drop for GPUTexture {
@@ -152,3 +177,45 @@ pub struct GPUTextureCreateInfo {
     pub format: GPUTextureFormat,
     pub access: GPUTextureAccess,
 }
+
+#[derive(Debug, Clone, Copy)]
+pub struct GPUTextureRegion {
+    pub mip_level: u32,
+    pub layer: u32,
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
+    pub w: u32,
+    pub h: u32,
+    pub d: u32,
+}
+
+impl GPUTextureRegion {
+    pub fn new(x: u32, y: u32, w: u32, h: u32) -> Self {
+        GPUTextureRegion {
+            mip_level: 0,
+            layer: 0,
+            x,
+            y,
+            z: 0,
+            w,
+            h,
+            d: 1,
+        }
+    }
+
+    /// Region covering the whole base level of the texture.
+    pub fn full(texture: &GPUTexture) -> Self {
+        Self::new(0, 0, texture.size.w as u32, texture.size.h as u32)
+    }
+
+    pub fn with_mip_level(mut self, mip_level: u32) -> Self {
+        self.mip_level = mip_level;
+        self
+    }
+
+    pub fn with_layer(mut self, layer: u32) -> Self {
+        self.layer = layer;
+        self
+    }
+}
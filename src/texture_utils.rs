@@ -1,6 +1,6 @@
-use sdl3_sys::gpu;
+use sdl3_sys::{gpu, surface};
 
-use crate::texture::{GPUTextureAccess, GPUTextureFormat};
+use crate::texture::{GPUFilter, GPUFlipMode, GPULoadOp, GPUTextureAccess, GPUTextureFormat};
 
 pub fn gpu_texture_format_to_sdl(format: GPUTextureFormat) -> gpu::SDL_GPUTextureFormat {
     match format {
@@ -225,6 +225,9 @@ pub fn gpu_texture_access_to_sdl(access: GPUTextureAccess) -> gpu::SDL_GPUTextur
     match access {
         GPUTextureAccess::Sampler => gpu::SDL_GPU_TEXTUREUSAGE_SAMPLER,
         GPUTextureAccess::RenderTarget => gpu::SDL_GPU_TEXTUREUSAGE_COLOR_TARGET,
+        GPUTextureAccess::SampledRenderTarget => {
+            gpu::SDL_GPU_TEXTUREUSAGE_COLOR_TARGET | gpu::SDL_GPU_TEXTUREUSAGE_SAMPLER
+        }
         GPUTextureAccess::DepthStencil => gpu::SDL_GPU_TEXTUREUSAGE_DEPTH_STENCIL_TARGET,
         GPUTextureAccess::GraphicsStorage => gpu::SDL_GPU_TEXTUREUSAGE_GRAPHICS_STORAGE_READ,
         GPUTextureAccess::ComputeStorageRead => gpu::SDL_GPU_TEXTUREUSAGE_COMPUTE_STORAGE_READ,
@@ -235,6 +238,29 @@ pub fn gpu_texture_access_to_sdl(access: GPUTextureAccess) -> gpu::SDL_GPUTextur
     }
 }
 
+pub fn gpu_filter_to_sdl(filter: GPUFilter) -> gpu::SDL_GPUFilter {
+    match filter {
+        GPUFilter::Nearest => gpu::SDL_GPU_FILTER_NEAREST,
+        GPUFilter::Linear => gpu::SDL_GPU_FILTER_LINEAR,
+    }
+}
+
+pub fn gpu_flip_mode_to_sdl(flip_mode: GPUFlipMode) -> surface::SDL_FlipMode {
+    match flip_mode {
+        GPUFlipMode::None => surface::SDL_FLIP_NONE,
+        GPUFlipMode::Horizontal => surface::SDL_FLIP_HORIZONTAL,
+        GPUFlipMode::Vertical => surface::SDL_FLIP_VERTICAL,
+    }
+}
+
+pub fn gpu_load_op_to_sdl(load_op: GPULoadOp) -> gpu::SDL_GPULoadOp {
+    match load_op {
+        GPULoadOp::Load => gpu::SDL_GPU_LOADOP_LOAD,
+        GPULoadOp::Clear => gpu::SDL_GPU_LOADOP_CLEAR,
+        GPULoadOp::DontCare => gpu::SDL_GPU_LOADOP_DONT_CARE,
+    }
+}
+
 pub fn gpu_texture_format_to_byte_size(format: GPUTextureFormat) -> u32 {
     match format {
         GPUTextureFormat::R8 => 1,
@@ -1,37 +1,210 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
-use sdl3_sys::{
-    events::{self, SDL_Event},
-    video,
+use image::ImageReader;
+use sdl3_sys::{clipboard, keyboard, mouse, pixels, rect, stdinc, surface, video};
+
+use crate::{
+    cursor::Cursor,
+    device::GPUDevice,
+    display::{display_mode_to_sdl, sdl_to_display_mode, Display, DisplayMode},
+    error::Error,
+    event::{Event, EventPump},
+    math::Rect,
+    sdl::Sdl,
 };
 
-use crate::{device::GPUDevice, math::Rect};
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowPosition {
+    /// Let the window manager decide.
+    Default,
+    Centered,
+    At(i32, i32),
+}
+
+fn window_position_to_sdl(position: WindowPosition) -> (i32, i32) {
+    match position {
+        WindowPosition::Default => (
+            video::SDL_WINDOWPOS_UNDEFINED,
+            video::SDL_WINDOWPOS_UNDEFINED,
+        ),
+        WindowPosition::Centered => (video::SDL_WINDOWPOS_CENTERED, video::SDL_WINDOWPOS_CENTERED),
+        WindowPosition::At(x, y) => (x, y),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FullscreenMode {
+    Windowed,
+    /// Fullscreen window at the desktop resolution, switches instantly.
+    Borderless,
+    /// Changes the display mode while the window has focus.
+    Exclusive(DisplayMode),
+}
+
+/// Configures a window before it is created. The window is created hidden and
+/// only shown once every option has been applied, so it never flashes at the
+/// wrong size or position.
+#[derive(Debug, Clone)]
+pub struct WindowBuilder {
+    sdl: Arc<Sdl>,
+    title: String,
+    width: i32,
+    height: i32,
+    flags: video::SDL_WindowFlags,
+    position: WindowPosition,
+    minimum_size: Option<(i32, i32)>,
+    maximum_size: Option<(i32, i32)>,
+    icon: Option<String>,
+}
+
+#[allow(dead_code)]
+impl WindowBuilder {
+    pub fn new(sdl: &Arc<Sdl>, title: &str, width: i32, height: i32) -> Self {
+        WindowBuilder {
+            sdl: Arc::clone(sdl),
+            title: title.to_string(),
+            width,
+            height,
+            flags: 0,
+            position: WindowPosition::Default,
+            minimum_size: None,
+            maximum_size: None,
+            icon: None,
+        }
+    }
+
+    fn flag(mut self, flag: video::SDL_WindowFlags, enabled: bool) -> Self {
+        if enabled {
+            self.flags |= flag;
+        } else {
+            self.flags &= !flag;
+        }
+
+        self
+    }
+
+    pub fn resizable(self, resizable: bool) -> Self {
+        self.flag(video::SDL_WINDOW_RESIZABLE, resizable)
+    }
+
+    pub fn borderless(self, borderless: bool) -> Self {
+        self.flag(video::SDL_WINDOW_BORDERLESS, borderless)
+    }
+
+    /// Request a full resolution backbuffer on high-DPI displays.
+    pub fn high_pixel_density(self, high_pixel_density: bool) -> Self {
+        self.flag(video::SDL_WINDOW_HIGH_PIXEL_DENSITY, high_pixel_density)
+    }
+
+    pub fn hidden(self, hidden: bool) -> Self {
+        self.flag(video::SDL_WINDOW_HIDDEN, hidden)
+    }
+
+    pub fn always_on_top(self, always_on_top: bool) -> Self {
+        self.flag(video::SDL_WINDOW_ALWAYS_ON_TOP, always_on_top)
+    }
+
+    pub fn maximized(self, maximized: bool) -> Self {
+        self.flag(video::SDL_WINDOW_MAXIMIZED, maximized)
+    }
+
+    pub fn fullscreen(self, fullscreen: bool) -> Self {
+        self.flag(video::SDL_WINDOW_FULLSCREEN, fullscreen)
+    }
+
+    pub fn position(mut self, x: i32, y: i32) -> Self {
+        self.position = WindowPosition::At(x, y);
+        self
+    }
+
+    pub fn centered(mut self) -> Self {
+        self.position = WindowPosition::Centered;
+        self
+    }
+
+    pub fn minimum_size(mut self, width: i32, height: i32) -> Self {
+        self.minimum_size = Some((width, height));
+        self
+    }
+
+    pub fn maximum_size(mut self, width: i32, height: i32) -> Self {
+        self.maximum_size = Some((width, height));
+        self
+    }
+
+    /// Path to an image file used as the window icon.
+    pub fn icon(mut self, path: &str) -> Self {
+        self.icon = Some(path.to_string());
+        self
+    }
+
+    pub fn build(&self) -> Result<Arc<Window>, Error> {
+        let cstr = title_to_cstring(&self.title)?;
+
+        let flags = self.flags | video::SDL_WINDOW_HIDDEN;
+        let window =
+            unsafe { video::SDL_CreateWindow(cstr.as_ptr(), self.width, self.height, flags) };
+        if window.is_null() {
+            return Err(Error::sdl("Failed to create window"));
+        }
+
+        // Wrap right away so the window is destroyed if any of the steps below fail.
+        let res = Arc::new(Window {
+            window,
+            cursor: Mutex::new(None),
+            sdl: Arc::clone(&self.sdl),
+        });
+
+        if let Some((width, height)) = self.minimum_size {
+            res.set_minimum_size(width, height)?;
+        }
+
+        if let Some((width, height)) = self.maximum_size {
+            res.set_maximum_size(width, height)?;
+        }
+
+        if self.position != WindowPosition::Default {
+            let (x, y) = window_position_to_sdl(self.position);
+            res.set_window_position(x, y);
+        }
+
+        if let Some(path) = &self.icon {
+            res.set_icon(path)?;
+        }
+
+        if self.flags & video::SDL_WINDOW_HIDDEN == 0 {
+            res.show()?;
+        }
+
+        Ok(res)
+    }
+}
 
 #[derive(Debug)]
 pub struct Window {
     window: *mut video::SDL_Window,
+    // Declared before `sdl` so the cursor is dropped while SDL is still up.
+    cursor: Mutex<Option<Arc<Cursor>>>,
+    sdl: Arc<Sdl>,
 }
 
 #[allow(dead_code)]
 impl Window {
-    pub fn new(title: &str, width: i32, height: i32) -> Arc<Self> {
-        let cstr = std::ffi::CString::new(title).unwrap();
-
-        let window = unsafe { video::SDL_CreateWindow(cstr.as_ptr(), width, height, 0) };
+    pub fn new(sdl: &Arc<Sdl>, title: &str, width: i32, height: i32) -> Result<Arc<Self>, Error> {
+        WindowBuilder::new(sdl, title, width, height).build()
+    }
 
-        Arc::new(Self { window })
+    /// SDL window id, matching `Event::window_id` for events targeted at this window.
+    pub fn id(&self) -> u32 {
+        unsafe { video::SDL_GetWindowID(self.window) }
     }
 
-    pub fn should_close(&self) -> bool {
-        let mut raw: SDL_Event = unsafe { std::mem::zeroed() };
-        unsafe { events::SDL_PollEvent(&mut raw) };
+    pub fn event_pump(&self) -> EventPump {
+        self.sdl.event_pump()
+    }
 
-        unsafe {
-            match events::SDL_EventType(raw.r#type) {
-                events::SDL_EVENT_QUIT => true,
-                _ => false,
-            }
-        }
+    pub fn sdl(&self) -> &Arc<Sdl> {
+        &self.sdl
     }
 
     pub fn get_window(&self) -> *mut video::SDL_Window {
@@ -60,17 +233,74 @@ impl Window {
         Rect::new(0, 0, width, height)
     }
 
-    pub fn set_window_title(&self, title: &str) {
-        let cstr = std::ffi::CString::new(title).unwrap();
+    /// Size of the drawable area in pixels. On high-DPI displays this is larger
+    /// than `get_window_size`, which is in points.
+    pub fn pixel_size(&self) -> Rect {
+        let mut width: i32 = 0;
+        let mut height: i32 = 0;
+        unsafe {
+            video::SDL_GetWindowSizeInPixels(self.window, &mut width, &mut height);
+        }
+
+        Rect::new(0, 0, width, height)
+    }
+
+    /// The new pixel size if `event` reports that this window was resized.
+    pub fn resize_event(&self, event: &Event) -> Option<Rect> {
+        match event {
+            Event::WindowPixelSizeChanged {
+                window_id,
+                width,
+                height,
+            } if *window_id == self.id() => Some(Rect::new(0, 0, *width, *height)),
+            _ => None,
+        }
+    }
+
+    /// Whether `event` is targeted at this window.
+    pub fn owns_event(&self, event: &Event) -> bool {
+        event.window_id() == Some(self.id())
+    }
+
+    /// The path of a file dropped on this window, if `event` is one.
+    pub fn dropped_file<'a>(&self, event: &'a Event) -> Option<&'a str> {
+        match event {
+            Event::DropFile {
+                window_id, path, ..
+            } if *window_id == self.id() => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Ratio of pixels to points, e.g. 2.0 on Retina displays.
+    pub fn pixel_density(&self) -> f32 {
+        unsafe { video::SDL_GetWindowPixelDensity(self.window) }
+    }
+
+    /// Content scale requested by the user for this window's display, including
+    /// the pixel density. Multiply UI sizes by this to keep them readable.
+    pub fn display_scale(&self) -> f32 {
+        unsafe { video::SDL_GetWindowDisplayScale(self.window) }
+    }
+
+    pub fn set_window_title(&self, title: &str) -> Result<(), Error> {
+        let cstr = title_to_cstring(title)?;
         unsafe {
             video::SDL_SetWindowTitle(self.window, cstr.as_ptr());
         }
+
+        Ok(())
     }
 
+    /// Invalid UTF-8 in the title is replaced rather than treated as an error.
     pub fn get_window_title(&self) -> String {
         let title = unsafe { video::SDL_GetWindowTitle(self.window) };
+        if title.is_null() {
+            return String::new();
+        }
+
         let cstr = unsafe { std::ffi::CStr::from_ptr(title) };
-        cstr.to_str().unwrap().to_string()
+        cstr.to_string_lossy().into_owned()
     }
 
     pub fn set_window_fullscreen(&self, fullscreen: bool) {
@@ -79,11 +309,326 @@ impl Window {
         }
     }
 
-    pub fn create_gpu_device(self: &Arc<Window>) -> Result<Arc<GPUDevice>, String> {
+    /// Switch between windowed, borderless desktop fullscreen and exclusive
+    /// fullscreen with a specific display mode.
+    pub fn set_fullscreen_mode(&self, mode: FullscreenMode) -> Result<(), Error> {
+        let res = match mode {
+            FullscreenMode::Windowed => unsafe {
+                video::SDL_SetWindowFullscreen(self.window, false)
+            },
+            FullscreenMode::Borderless => unsafe {
+                video::SDL_SetWindowFullscreenMode(self.window, std::ptr::null())
+                    && video::SDL_SetWindowFullscreen(self.window, true)
+            },
+            FullscreenMode::Exclusive(mode) => {
+                let mode = display_mode_to_sdl(&mode)?;
+                unsafe {
+                    video::SDL_SetWindowFullscreenMode(self.window, &mode)
+                        && video::SDL_SetWindowFullscreen(self.window, true)
+                }
+            }
+        };
+
+        Self::check(res, "Failed to set fullscreen mode")
+    }
+
+    pub fn get_fullscreen_mode(&self) -> FullscreenMode {
+        if self.flags() & video::SDL_WINDOW_FULLSCREEN == 0 {
+            return FullscreenMode::Windowed;
+        }
+
+        let mode = unsafe { video::SDL_GetWindowFullscreenMode(self.window) };
+        if mode.is_null() {
+            return FullscreenMode::Borderless;
+        }
+
+        FullscreenMode::Exclusive(sdl_to_display_mode(unsafe { &*mode }))
+    }
+
+    /// The display containing the center of the window.
+    pub fn display(&self) -> Result<Display, Error> {
+        let id = unsafe { video::SDL_GetDisplayForWindow(self.window) };
+        if id == 0 {
+            return Err(Error::sdl("Failed to get display for window"));
+        }
+
+        Ok(Display { id })
+    }
+
+    /// Center the window on `display`. A fullscreen window moves to that display.
+    pub fn move_to_display(&self, display: &Display) {
+        let position = video::SDL_WINDOWPOS_CENTERED_DISPLAY(display.id);
+        self.set_window_position(position, position);
+    }
+
+    fn check(res: bool, context: &str) -> Result<(), Error> {
+        if !res {
+            return Err(Error::sdl(context));
+        }
+
+        Ok(())
+    }
+
+    pub fn set_resizable(&self, resizable: bool) -> Result<(), Error> {
+        let res = unsafe { video::SDL_SetWindowResizable(self.window, resizable) };
+        Self::check(res, "Failed to set window resizable")
+    }
+
+    pub fn set_borderless(&self, borderless: bool) -> Result<(), Error> {
+        let res = unsafe { video::SDL_SetWindowBordered(self.window, !borderless) };
+        Self::check(res, "Failed to set window bordered")
+    }
+
+    pub fn set_always_on_top(&self, always_on_top: bool) -> Result<(), Error> {
+        let res = unsafe { video::SDL_SetWindowAlwaysOnTop(self.window, always_on_top) };
+        Self::check(res, "Failed to set window always on top")
+    }
+
+    pub fn set_minimum_size(&self, width: i32, height: i32) -> Result<(), Error> {
+        let res = unsafe { video::SDL_SetWindowMinimumSize(self.window, width, height) };
+        Self::check(res, "Failed to set window minimum size")
+    }
+
+    pub fn set_maximum_size(&self, width: i32, height: i32) -> Result<(), Error> {
+        let res = unsafe { video::SDL_SetWindowMaximumSize(self.window, width, height) };
+        Self::check(res, "Failed to set window maximum size")
+    }
+
+    pub fn center(&self) {
+        let (x, y) = window_position_to_sdl(WindowPosition::Centered);
+        self.set_window_position(x, y);
+    }
+
+    pub fn show(&self) -> Result<(), Error> {
+        let res = unsafe { video::SDL_ShowWindow(self.window) };
+        Self::check(res, "Failed to show window")
+    }
+
+    pub fn hide(&self) -> Result<(), Error> {
+        let res = unsafe { video::SDL_HideWindow(self.window) };
+        Self::check(res, "Failed to hide window")
+    }
+
+    pub fn maximize(&self) -> Result<(), Error> {
+        let res = unsafe { video::SDL_MaximizeWindow(self.window) };
+        Self::check(res, "Failed to maximize window")
+    }
+
+    pub fn minimize(&self) -> Result<(), Error> {
+        let res = unsafe { video::SDL_MinimizeWindow(self.window) };
+        Self::check(res, "Failed to minimize window")
+    }
+
+    pub fn restore(&self) -> Result<(), Error> {
+        let res = unsafe { video::SDL_RestoreWindow(self.window) };
+        Self::check(res, "Failed to restore window")
+    }
+
+    pub fn flags(&self) -> video::SDL_WindowFlags {
+        unsafe { video::SDL_GetWindowFlags(self.window) }
+    }
+
+    pub fn is_resizable(&self) -> bool {
+        self.flags() & video::SDL_WINDOW_RESIZABLE != 0
+    }
+
+    pub fn is_borderless(&self) -> bool {
+        self.flags() & video::SDL_WINDOW_BORDERLESS != 0
+    }
+
+    pub fn is_hidden(&self) -> bool {
+        self.flags() & video::SDL_WINDOW_HIDDEN != 0
+    }
+
+    pub fn is_maximized(&self) -> bool {
+        self.flags() & video::SDL_WINDOW_MAXIMIZED != 0
+    }
+
+    /// Load an image file and use it as the window icon.
+    pub fn set_icon(&self, path: &str) -> Result<(), Error> {
+        // SDL keeps its own copy of the icon.
+        let res = with_image_surface(path, |icon| unsafe {
+            video::SDL_SetWindowIcon(self.window, icon)
+        })?;
+
+        Self::check(res, "Failed to set window icon")
+    }
+
+    /// Use `cursor` while the mouse is over any window. The window keeps the
+    /// cursor alive until another one is set.
+    pub fn set_cursor(&self, cursor: &Arc<Cursor>) -> Result<(), Error> {
+        let res = unsafe { mouse::SDL_SetCursor(cursor.get_cursor()) };
+        Self::check(res, "Failed to set cursor")?;
+
+        *self.cursor.lock().unwrap() = Some(Arc::clone(cursor));
+
+        Ok(())
+    }
+
+    pub fn show_cursor(&self) -> Result<(), Error> {
+        let res = unsafe { mouse::SDL_ShowCursor() };
+        Self::check(res, "Failed to show cursor")
+    }
+
+    pub fn hide_cursor(&self) -> Result<(), Error> {
+        let res = unsafe { mouse::SDL_HideCursor() };
+        Self::check(res, "Failed to hide cursor")
+    }
+
+    pub fn is_cursor_visible(&self) -> bool {
+        unsafe { mouse::SDL_CursorVisible() }
+    }
+
+    /// Confine the mouse to the window.
+    pub fn set_mouse_grab(&self, grabbed: bool) -> Result<(), Error> {
+        let res = unsafe { video::SDL_SetWindowMouseGrab(self.window, grabbed) };
+        Self::check(res, "Failed to set mouse grab")
+    }
+
+    pub fn is_mouse_grabbed(&self) -> bool {
+        unsafe { video::SDL_GetWindowMouseGrab(self.window) }
+    }
+
+    /// Keep receiving mouse events while a button is held and the mouse leaves
+    /// the window, e.g. while dragging a slider.
+    pub fn set_mouse_capture(&self, captured: bool) -> Result<(), Error> {
+        let res = unsafe { mouse::SDL_CaptureMouse(captured) };
+        Self::check(res, "Failed to capture mouse")
+    }
+
+    /// Hide the cursor and report only relative motion, without the cursor
+    /// stopping at the window or screen edge. Meant for camera controls.
+    pub fn set_relative_mouse_mode(&self, enabled: bool) -> Result<(), Error> {
+        let res = unsafe { mouse::SDL_SetWindowRelativeMouseMode(self.window, enabled) };
+        Self::check(res, "Failed to set relative mouse mode")
+    }
+
+    pub fn is_relative_mouse_mode(&self) -> bool {
+        unsafe { mouse::SDL_GetWindowRelativeMouseMode(self.window) }
+    }
+
+    /// Start delivering `TextInput` and `TextEditing` events for this window.
+    /// On platforms with an on-screen keyboard this also shows it.
+    pub fn start_text_input(&self) -> Result<(), Error> {
+        let res = unsafe { keyboard::SDL_StartTextInput(self.window) };
+        Self::check(res, "Failed to start text input")
+    }
+
+    pub fn stop_text_input(&self) -> Result<(), Error> {
+        let res = unsafe { keyboard::SDL_StopTextInput(self.window) };
+        Self::check(res, "Failed to stop text input")
+    }
+
+    pub fn is_text_input_active(&self) -> bool {
+        unsafe { keyboard::SDL_TextInputActive(self.window) }
+    }
+
+    /// Tell the IME where the text field is, in window coordinates, so the
+    /// candidate list is placed next to it. `cursor` is the offset of the
+    /// caret from the left of `area`.
+    pub fn set_text_input_area(&self, area: Rect, cursor: i32) -> Result<(), Error> {
+        let area = rect::SDL_Rect {
+            x: area.x,
+            y: area.y,
+            w: area.w,
+            h: area.h,
+        };
+
+        let res = unsafe { keyboard::SDL_SetTextInputArea(self.window, &area, cursor) };
+        Self::check(res, "Failed to set text input area")
+    }
+
+    pub fn get_clipboard_text(&self) -> Result<String, Error> {
+        let text = unsafe { clipboard::SDL_GetClipboardText() };
+        if text.is_null() {
+            return Err(Error::sdl("Failed to get clipboard text"));
+        }
+
+        let res = unsafe { std::ffi::CStr::from_ptr(text) }
+            .to_string_lossy()
+            .into_owned();
+
+        unsafe {
+            stdinc::SDL_free(text as *mut std::ffi::c_void);
+        }
+
+        Ok(res)
+    }
+
+    pub fn set_clipboard_text(&self, text: &str) -> Result<(), Error> {
+        let cstr = match std::ffi::CString::new(text) {
+            Ok(cstr) => cstr,
+            Err(_err) => {
+                return Err(Error::InvalidUsage(
+                    "Clipboard text must not contain a nul byte".to_string(),
+                ))
+            }
+        };
+
+        let res = unsafe { clipboard::SDL_SetClipboardText(cstr.as_ptr()) };
+        Self::check(res, "Failed to set clipboard text")
+    }
+
+    pub fn has_clipboard_text(&self) -> bool {
+        unsafe { clipboard::SDL_HasClipboardText() }
+    }
+
+    pub fn create_gpu_device(self: &Arc<Window>) -> Result<Arc<GPUDevice>, Error> {
         GPUDevice::new(self)
     }
 }
 
+/// Decode an image file and pass it to `f` as a temporary RGBA surface. The
+/// surface borrows the decoded pixels and is destroyed once `f` returns.
+pub fn with_image_surface<R>(
+    path: &str,
+    f: impl FnOnce(*mut surface::SDL_Surface) -> R,
+) -> Result<R, Error> {
+    let img = ImageReader::open(path);
+    if let Err(err) = img {
+        return Err(Error::Image(format!("Failed to open image: {:?}", err)));
+    }
+
+    let img = img.unwrap().decode();
+    if let Err(err) = img {
+        return Err(Error::Image(format!("Failed to decode image: {:?}", err)));
+    }
+
+    let mut img = img.unwrap().into_rgba8();
+    let width = img.width() as i32;
+    let height = img.height() as i32;
+
+    let ptr = unsafe {
+        surface::SDL_CreateSurfaceFrom(
+            width,
+            height,
+            pixels::SDL_PIXELFORMAT_RGBA32,
+            img.as_mut_ptr() as *mut std::ffi::c_void,
+            width * 4,
+        )
+    };
+
+    if ptr.is_null() {
+        return Err(Error::sdl("Failed to create surface"));
+    }
+
+    let res = f(ptr);
+    unsafe {
+        surface::SDL_DestroySurface(ptr);
+    }
+
+    Ok(res)
+}
+
+fn title_to_cstring(title: &str) -> Result<std::ffi::CString, Error> {
+    match std::ffi::CString::new(title) {
+        Ok(cstr) => Ok(cstr),
+        Err(_err) => Err(Error::InvalidUsage(
+            "Window title must not contain a nul byte".to_string(),
+        )),
+    }
+}
+
 impl Drop for Window {
     fn drop(&mut self) {
         unsafe {